@@ -31,7 +31,7 @@ fn main() {
     println!("{} Recent posts for {} at {}", x, rs.user, rs.date);
     for p in rs.posts {
         println!("- {}\n  {}", p.description, p.href);
-        if p.tags.len() > 0 {
+        if !p.tags.is_empty() {
             let tgs: Vec<_> = p.tags.split(" ").collect();
             let tstr = tgs.join(", ");
             println!("  ({})", tstr);
@@ -50,7 +50,7 @@ fn main() {
     let res: AllResults = dates_endpoint.query(&pb).unwrap();
     println!("`AllResults` Struture");
     println!("user: {}", res.user);
-    if res.tag != "" {
+    if !res.tag.is_empty() {
         println!("tags: {}", res.tag);
     }
     println!("Dates: (Date :: Count)");
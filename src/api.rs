@@ -16,9 +16,13 @@ mod client;
 mod endpoint;
 mod error;
 mod ignore;
+mod instrument;
+mod paged;
 mod params;
 pub(crate) mod query;
 mod raw;
+mod response;
+mod with_response;
 
 pub mod v1;
 pub mod v2;
@@ -30,19 +34,34 @@ pub use self::client::AsyncClient;
 pub use self::client::Client;
 pub use self::client::RestClient;
 
+pub use self::endpoint::ApiVersion;
 pub use self::endpoint::Endpoint;
 
 pub use self::query::AsyncQuery;
 pub use self::query::Query;
 
+pub use self::params::FormParams;
 pub use self::params::ParamValue;
 pub use self::params::QueryParams;
 
 pub use self::error::ApiError;
 pub use self::error::BodyError;
+pub use self::error::ResponseContext;
 
 pub use self::ignore::ignore;
 pub use self::ignore::Ignore;
 
+pub use self::instrument::record_limit_wait;
+pub use self::instrument::record_retries;
+
+pub use self::paged::paged;
+pub use self::paged::Pageable;
+pub use self::paged::Paged;
+pub use self::paged::Pagination;
+
 pub use self::raw::raw;
 pub use self::raw::Raw;
+
+pub use self::with_response::with_response;
+pub use self::with_response::TypedResponse;
+pub use self::with_response::WithResponse;
@@ -12,7 +12,7 @@ use http::request::Builder as RequestBuilder;
 use http::Response;
 use url::Url;
 
-use crate::api::ApiError;
+use crate::api::{ApiError, ApiVersion};
 
 /// A trait representing a REST-ful client
 pub trait RestClient {
@@ -21,8 +21,13 @@ pub trait RestClient {
 
     /// Generate the URL for the endpoint for this client
     ///
-    ///
-    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>>;
+    /// The `version` of the endpoint decides the base path and any
+    /// default parameters added to the URL.
+    fn rest_endpoint(
+        &self,
+        version: ApiVersion,
+        endpoint: &str,
+    ) -> Result<Url, ApiError<Self::Error>>;
 }
 
 /// A trait representing a client which can communicate with Pinboard
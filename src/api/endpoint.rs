@@ -6,10 +6,29 @@
 
 use std::borrow::Cow;
 
-use http::{self, header, Method, Request};
+use async_trait::async_trait;
+use http::{self, Method};
 use serde::de::DeserializeOwned;
 
-use crate::api::{query, ApiError, BodyError, Client, Query, QueryParams};
+use crate::api::{
+    query, response, ApiError, AsyncClient, AsyncQuery, BodyError, Client, Query, QueryParams,
+};
+
+/// The version of the Pinboard API an endpoint belongs to.
+///
+/// Clients use the version to decide the base path, how to pass the
+/// authentication token and which default parameters to send.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ApiVersion {
+    /// The [V1 API](https://pinboard.in/api/)
+    ///
+    /// V1 endpoint paths carry their own `v1/` prefix.
+    #[default]
+    V1,
+    /// The [V2 API](https://pinboard.in/api/v2/overview)
+    V2,
+}
 
 /// A trait for providing the necessary informatino for a single REST API endpoint.
 pub trait Endpoint {
@@ -18,8 +37,13 @@ pub trait Endpoint {
     /// The path to the endpoint.
     fn endpoint(&self) -> Cow<'static, str>;
 
+    /// The API version of the endpoint.
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V1
+    }
+
     /// Query parameters for the endpoint.
-    fn parameters(&self) -> QueryParams {
+    fn parameters(&self) -> QueryParams<'_> {
         QueryParams::default()
     }
 
@@ -38,25 +62,18 @@ where
     C: Client,
 {
     fn query(&self, client: &C) -> Result<T, ApiError<C::Error>> {
-        let mut url = client.rest_endpoint(&self.endpoint())?;
-        self.parameters().add_to_url(&mut url);
-
-        let req = Request::builder()
-            .method(self.method())
-            .uri(query::url_to_http_uri(url));
-        let (req, data) = if let Some((mime, data)) = self.body()? {
-            let req = req.header(header::CONTENT_TYPE, mime);
-            (req, data)
-        } else {
-            (req, Vec::new())
-        };
-        let rsp = client.rest(req, data)?;
-        let status = rsp.status();
-        let v = serde_json::from_slice(rsp.body())?;
-        if !status.is_success() {
-            return Err(ApiError::from_pinboard(v));
-        }
-
-        serde_json::from_value::<T>(v).map_err(ApiError::data_type::<T>)
+        query::send(self, client, |url, rsp| response::decode(url, &rsp))
+    }
+}
+
+#[async_trait]
+impl<E, T, C> AsyncQuery<T, C> for E
+where
+    E: Endpoint + Sync,
+    T: DeserializeOwned + 'static,
+    C: AsyncClient + Sync,
+{
+    async fn query_async(&self, client: &C) -> Result<T, ApiError<C::Error>> {
+        query::send_async(self, client, |url, rsp| response::decode(url, &rsp)).await
     }
 }
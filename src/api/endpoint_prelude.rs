@@ -13,8 +13,10 @@ pub use std::borrow::Cow;
 
 pub use http::Method;
 
+pub use crate::api::ApiVersion;
 pub use crate::api::BodyError;
 pub use crate::api::Client;
 pub use crate::api::Endpoint;
-// pub use crate::api::FormParams;
+pub use crate::api::FormParams;
+pub use crate::api::Pageable;
 pub use crate::api::QueryParams;
@@ -6,9 +6,16 @@
 
 use std::any;
 use std::error::Error;
+use std::fmt;
+use std::io;
+
+use http::{HeaderMap, StatusCode};
+use url::Url;
 
 use thiserror::Error;
 
+use crate::auth;
+
 /// Errors which may occur when using API endpoints.
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -40,6 +47,11 @@ where
     },
 
     /// JSON deserialization failed
+    ///
+    /// Responses decoded by this crate report [`ApiError::TruncatedJson`],
+    /// [`ApiError::InvalidJson`] or [`ApiError::DataType`] instead. This is
+    /// only produced by converting a `serde_json::Error`, e.g. with `?` in a
+    /// custom [`Query`](crate::api::Query) implementation.
     #[error("could not parse JSON response: {}", source)]
     Json {
         /// the source of the error.
@@ -48,42 +60,133 @@ where
     },
 
     /// Pinboard returned an error without JSON information.
-    #[error("pinboard internal server error {}", status)]
+    #[error("pinboard internal server error {} from {}", status, context)]
     PinboardService {
         /// The status code for the return.
         status: http::StatusCode,
         /// The error data from Pinboard.
         data: Vec<u8>,
+        /// The request and response the error came from.
+        context: ResponseContext,
+    },
+
+    /// Pinboard rejected the credentials.
+    #[error("pinboard authentication failed {} from {}", status, context)]
+    Auth {
+        /// The status code for the return.
+        status: http::StatusCode,
+        /// The error data from Pinboard.
+        data: Vec<u8>,
+        /// The request and response the error came from.
+        context: ResponseContext,
+    },
+
+    /// Pinboard returned an empty body where data was expected.
+    #[error("pinboard returned an empty response {} from {}", status, context)]
+    EmptyResponse {
+        /// The status code for the return.
+        status: http::StatusCode,
+        /// The request and response the error came from.
+        context: ResponseContext,
+    },
+
+    /// Pinboard returned an HTML page, usually from its frontend.
+    #[error(
+        "pinboard returned an HTML page {} from {}: {}",
+        status,
+        context,
+        excerpt
+    )]
+    HtmlResponse {
+        /// The status code for the return.
+        status: http::StatusCode,
+        /// The start of the page, or its title.
+        excerpt: String,
+        /// The request and response the error came from.
+        context: ResponseContext,
+    },
+
+    /// The JSON response ended early.
+    #[error("truncated JSON response {} from {}: {}", status, context, source)]
+    TruncatedJson {
+        /// The source of the error.
+        source: serde_json::Error,
+        /// The status code for the return.
+        status: http::StatusCode,
+        /// The start of the body.
+        excerpt: String,
+        /// The request and response the error came from.
+        context: ResponseContext,
+    },
+
+    /// The response was not valid JSON.
+    #[error(
+        "invalid JSON response {} from {}: {}: {}",
+        status,
+        context,
+        source,
+        excerpt
+    )]
+    InvalidJson {
+        /// The source of the error.
+        source: serde_json::Error,
+        /// The status code for the return.
+        status: http::StatusCode,
+        /// The start of the body.
+        excerpt: String,
+        /// The request and response the error came from.
+        context: ResponseContext,
     },
 
     /// Failed to parse and expected data type from JSON.
-    #[error("could not parse {} data from JSON: {}", typename, source)]
+    #[error(
+        "could not parse {} data from JSON from {}: {}",
+        typename,
+        context,
+        source
+    )]
     DataType {
         /// The source of the error
         source: serde_json::Error,
         /// The name of the type that could not be deserialized
         typename: &'static str,
+        /// The status code for the return.
+        status: http::StatusCode,
+        /// The request and response the error came from.
+        context: ResponseContext,
     },
 
     /// Pinboard returned an Error
-    #[error("{}", msg)]
+    #[error("{} ({} from {})", msg, status, context)]
     Pinboard {
         /// The error message
         msg: String,
+        /// The status code for the return.
+        status: http::StatusCode,
+        /// The request and response the error came from.
+        context: ResponseContext,
     },
 
     /// Pinboard returned an Error
-    #[error("{}", obj)]
+    #[error("{} ({} from {})", obj, status, context)]
     PinboardObject {
         /// The error message
         obj: serde_json::Value,
+        /// The status code for the return.
+        status: http::StatusCode,
+        /// The request and response the error came from.
+        context: ResponseContext,
     },
 
     /// Unrecognized Pinboard Error
-    #[error("{}", obj)]
+    #[error("{} ({} from {})", obj, status, context)]
     PinboardUnrecognized {
         /// The full object from Pinboard
         obj: serde_json::Value,
+        /// The status code for the return.
+        status: http::StatusCode,
+        /// The request and response the error came from.
+        context: ResponseContext,
     },
 }
 
@@ -98,6 +201,82 @@ pub enum BodyError {
         #[from]
         source: serde_urlencoded::ser::Error,
     },
+    /// Body data could not be serialized to JSON
+    #[error("failed to serialize JSON body: {}", source)]
+    Json {
+        /// the source of the error
+        #[from]
+        source: serde_json::Error,
+    },
+}
+
+/// Where an error response came from
+///
+/// The endpoint is the path and query of the request with the token
+/// removed. Only headers useful for diagnosing failures are kept: the
+/// request id, `Retry-After` and any rate limit headers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseContext {
+    // Boxed to keep `ApiError` small.
+    inner: Box<ContextInner>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct ContextInner {
+    endpoint: String,
+    headers: HeaderMap,
+}
+
+impl ResponseContext {
+    pub(crate) fn new(url: &Url, headers: &HeaderMap) -> Self {
+        let mut url = url.clone();
+        auth::redact_token(&mut url, None);
+        let endpoint = match url.query() {
+            Some(query) if !query.is_empty() => format!("{}?{}", url.path(), query),
+            _ => url.path().into(),
+        };
+
+        let headers = headers
+            .iter()
+            .filter(|(name, _)| {
+                let name = name.as_str();
+                name == "retry-after" || name == "x-request-id" || name.starts_with("x-ratelimit-")
+            })
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+
+        ResponseContext {
+            inner: Box::new(ContextInner { endpoint, headers }),
+        }
+    }
+
+    /// The endpoint of the request, with the token removed
+    pub fn endpoint(&self) -> &str {
+        &self.inner.endpoint
+    }
+
+    /// The response headers of interest
+    pub fn headers(&self) -> &HeaderMap {
+        &self.inner.headers
+    }
+
+    /// The request id assigned by the server, if any
+    pub fn request_id(&self) -> Option<&str> {
+        self.inner
+            .headers
+            .get("x-request-id")
+            .and_then(|value| value.to_str().ok())
+    }
+}
+
+impl fmt::Display for ResponseContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.inner.endpoint)?;
+        if let Some(id) = self.request_id() {
+            write!(f, " (request {})", id)?;
+        }
+        Ok(())
+    }
 }
 
 impl<E> ApiError<E>
@@ -109,34 +288,419 @@ where
         ApiError::Client { source }
     }
 
-    pub(crate) fn server_error(status: http::StatusCode, body: &bytes::Bytes) -> Self {
+    /// Convert the client error into another type
+    pub fn map_client<F, W>(self, f: F) -> ApiError<W>
+    where
+        F: FnOnce(E) -> W,
+        W: Error + Send + Sync + 'static,
+    {
+        match self {
+            ApiError::Client { source } => ApiError::client(f(source)),
+            ApiError::UrlParse { source } => ApiError::UrlParse { source },
+            ApiError::Body { source } => ApiError::Body { source },
+            ApiError::Json { source } => ApiError::Json { source },
+            ApiError::PinboardService {
+                status,
+                data,
+                context,
+            } => ApiError::PinboardService {
+                status,
+                data,
+                context,
+            },
+            ApiError::Auth {
+                status,
+                data,
+                context,
+            } => ApiError::Auth {
+                status,
+                data,
+                context,
+            },
+            ApiError::EmptyResponse { status, context } => {
+                ApiError::EmptyResponse { status, context }
+            },
+            ApiError::HtmlResponse {
+                status,
+                excerpt,
+                context,
+            } => ApiError::HtmlResponse {
+                status,
+                excerpt,
+                context,
+            },
+            ApiError::TruncatedJson {
+                source,
+                status,
+                excerpt,
+                context,
+            } => ApiError::TruncatedJson {
+                source,
+                status,
+                excerpt,
+                context,
+            },
+            ApiError::InvalidJson {
+                source,
+                status,
+                excerpt,
+                context,
+            } => ApiError::InvalidJson {
+                source,
+                status,
+                excerpt,
+                context,
+            },
+            ApiError::DataType {
+                source,
+                typename,
+                status,
+                context,
+            } => ApiError::DataType {
+                source,
+                typename,
+                status,
+                context,
+            },
+            ApiError::Pinboard {
+                msg,
+                status,
+                context,
+            } => ApiError::Pinboard {
+                msg,
+                status,
+                context,
+            },
+            ApiError::PinboardObject {
+                obj,
+                status,
+                context,
+            } => ApiError::PinboardObject {
+                obj,
+                status,
+                context,
+            },
+            ApiError::PinboardUnrecognized {
+                obj,
+                status,
+                context,
+            } => ApiError::PinboardUnrecognized {
+                obj,
+                status,
+                context,
+            },
+        }
+    }
+
+    /// The HTTP status of the response which caused the error, if known
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ApiError::PinboardService { status, .. }
+            | ApiError::Auth { status, .. }
+            | ApiError::EmptyResponse { status, .. }
+            | ApiError::HtmlResponse { status, .. }
+            | ApiError::TruncatedJson { status, .. }
+            | ApiError::InvalidJson { status, .. }
+            | ApiError::DataType { status, .. }
+            | ApiError::Pinboard { status, .. }
+            | ApiError::PinboardObject { status, .. }
+            | ApiError::PinboardUnrecognized { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Where the response which caused the error came from, if known
+    pub fn context(&self) -> Option<&ResponseContext> {
+        match self {
+            ApiError::PinboardService { context, .. }
+            | ApiError::Auth { context, .. }
+            | ApiError::EmptyResponse { context, .. }
+            | ApiError::HtmlResponse { context, .. }
+            | ApiError::TruncatedJson { context, .. }
+            | ApiError::InvalidJson { context, .. }
+            | ApiError::DataType { context, .. }
+            | ApiError::Pinboard { context, .. }
+            | ApiError::PinboardObject { context, .. }
+            | ApiError::PinboardUnrecognized { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// A short name for the kind of error, suitable as a metrics label
+    pub fn class(&self) -> &'static str {
+        match self {
+            ApiError::Client { .. } => "client",
+            ApiError::UrlParse { .. } => "url_parse",
+            ApiError::Body { .. } => "body",
+            ApiError::Json { .. } => "json",
+            ApiError::PinboardService { .. } => "service",
+            ApiError::Auth { .. } => "auth",
+            ApiError::EmptyResponse { .. } => "empty_response",
+            ApiError::HtmlResponse { .. } => "html_response",
+            ApiError::TruncatedJson { .. } => "truncated_json",
+            ApiError::InvalidJson { .. } => "invalid_json",
+            ApiError::DataType { .. } => "data_type",
+            ApiError::Pinboard { .. }
+            | ApiError::PinboardObject { .. }
+            | ApiError::PinboardUnrecognized { .. } => "pinboard",
+        }
+    }
+
+    /// Whether the credentials were rejected
+    pub fn is_auth_failure(&self) -> bool {
+        matches!(self, ApiError::Auth { .. })
+    }
+
+    /// Whether the call was rejected for exceeding the rate limits
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// Whether the requested item does not exist
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            ApiError::Pinboard { msg, .. } if msg.eq_ignore_ascii_case("item not found") => true,
+            _ => self.status() == Some(StatusCode::NOT_FOUND),
+        }
+    }
+
+    /// Whether the same call may succeed if tried again later
+    ///
+    /// This covers rate limiting, server errors, responses truncated on
+    /// success and transient network failures reported by the client.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ApiError::Client { source } => is_transient(source),
+            ApiError::TruncatedJson { status, .. } if status.is_success() => true,
+            _ => {
+                self.is_rate_limited()
+                    || self.status().is_some_and(|status| status.is_server_error())
+            },
+        }
+    }
+
+    pub(crate) fn auth_failure(
+        status: StatusCode,
+        context: &ResponseContext,
+        body: &bytes::Bytes,
+    ) -> Option<Self> {
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Some(ApiError::Auth {
+                status,
+                data: body.to_vec(),
+                context: context.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn server_error(
+        status: http::StatusCode,
+        context: &ResponseContext,
+        body: &bytes::Bytes,
+    ) -> Self {
         Self::PinboardService {
             status,
             data: body.into_iter().copied().collect(),
+            context: context.clone(),
         }
     }
 
-    pub(crate) fn from_pinboard(value: serde_json::Value) -> Self {
+    pub(crate) fn from_pinboard(
+        status: http::StatusCode,
+        context: &ResponseContext,
+        value: serde_json::Value,
+    ) -> Self {
         // TODO: This is now how pinboard returns errors
         let error_message = value.pointer("/error_message");
+        let context = context.clone();
 
         if let Some(error_message) = error_message {
             if let Some(msg) = error_message.as_str() {
-                ApiError::Pinboard { msg: msg.into() }
+                ApiError::Pinboard {
+                    msg: msg.into(),
+                    status,
+                    context,
+                }
             } else {
                 ApiError::PinboardObject {
                     obj: error_message.clone(),
+                    status,
+                    context,
                 }
             }
         } else {
-            ApiError::PinboardUnrecognized { obj: value }
+            ApiError::PinboardUnrecognized {
+                obj: value,
+                status,
+                context,
+            }
         }
     }
 
-    pub(crate) fn data_type<T>(source: serde_json::Error) -> Self {
+    pub(crate) fn data_type<T>(
+        source: serde_json::Error,
+        status: http::StatusCode,
+        context: &ResponseContext,
+    ) -> Self {
         ApiError::DataType {
             source,
             typename: any::type_name::<T>(),
+            status,
+            context: context.clone(),
         }
     }
 }
+
+// Look through the error chain for timeouts and dropped connections.
+fn is_transient(err: &(dyn Error + 'static)) -> bool {
+    let mut next = Some(err);
+    while let Some(err) = next {
+        #[cfg(feature = "reqwest")]
+        if let Some(err) = err.downcast_ref::<reqwest::Error>() {
+            if err.is_timeout() || err.is_connect() {
+                return true;
+            }
+        }
+        #[cfg(feature = "hyper")]
+        if let Some(err) = err.downcast_ref::<hyper::Error>() {
+            if err.is_timeout() || err.is_connect() {
+                return true;
+            }
+        }
+        // `UreqPinboard` boxes its transport errors.
+        #[cfg(feature = "ureq")]
+        if let Some(err) = err.downcast_ref::<Box<ureq::Transport>>() {
+            if matches!(
+                err.kind(),
+                ureq::ErrorKind::Dns | ureq::ErrorKind::ConnectionFailed | ureq::ErrorKind::Io
+            ) {
+                return true;
+            }
+        }
+        if let Some(err) = err.downcast_ref::<io::Error>() {
+            if matches!(
+                err.kind(),
+                io::ErrorKind::TimedOut
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::ConnectionRefused
+                    | io::ErrorKind::Interrupted
+            ) {
+                return true;
+            }
+        }
+        next = err.source();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use http::{HeaderMap, HeaderValue, StatusCode};
+    use thiserror::Error;
+    use url::Url;
+
+    use crate::api::{ApiError, ResponseContext};
+
+    #[derive(Debug, Error)]
+    #[error("transport")]
+    struct Transport {
+        #[source]
+        source: io::Error,
+    }
+
+    type TestError = ApiError<Transport>;
+
+    fn service(status: StatusCode) -> TestError {
+        ApiError::server_error(status, &ResponseContext::default(), &Vec::new().into())
+    }
+
+    #[test]
+    fn classify_status() {
+        assert!(service(StatusCode::TOO_MANY_REQUESTS).is_rate_limited());
+        assert!(service(StatusCode::TOO_MANY_REQUESTS).is_retryable());
+        assert!(service(StatusCode::BAD_GATEWAY).is_retryable());
+        assert!(service(StatusCode::NOT_FOUND).is_not_found());
+        assert!(!service(StatusCode::NOT_FOUND).is_retryable());
+        assert_eq!(
+            service(StatusCode::BAD_GATEWAY).status(),
+            Some(StatusCode::BAD_GATEWAY),
+        );
+    }
+
+    #[test]
+    fn classify_auth() {
+        for status in [StatusCode::UNAUTHORIZED, StatusCode::FORBIDDEN] {
+            let err =
+                TestError::auth_failure(status, &ResponseContext::default(), &Vec::new().into())
+                    .unwrap();
+            assert!(err.is_auth_failure());
+            assert!(!err.is_retryable());
+            assert_eq!(err.status(), Some(status));
+        }
+        assert!(TestError::auth_failure(
+            StatusCode::NOT_FOUND,
+            &ResponseContext::default(),
+            &Vec::new().into()
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn classify_pinboard_message() {
+        let err = TestError::Pinboard {
+            msg: "item not found".into(),
+            status: StatusCode::OK,
+            context: ResponseContext::default(),
+        };
+        assert!(err.is_not_found());
+        assert_eq!(err.status(), Some(StatusCode::OK));
+    }
+
+    #[test]
+    fn context_redacts_token() {
+        let url = Url::parse(
+            "https://api.pinboard.in/v1/posts/add?auth_token=user%3ASECRET&url=https%3A%2F%2Fexample.com%2F&format=json",
+        )
+        .unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", HeaderValue::from_static("abc123"));
+        headers.insert("retry-after", HeaderValue::from_static("3"));
+        headers.insert("set-cookie", HeaderValue::from_static("session=SECRET"));
+
+        let context = ResponseContext::new(&url, &headers);
+        assert_eq!(
+            context.endpoint(),
+            "/v1/posts/add?url=https%3A%2F%2Fexample.com%2F&format=json",
+        );
+        assert_eq!(context.request_id(), Some("abc123"));
+        assert_eq!(context.headers().len(), 2);
+
+        let err = TestError::from_pinboard(
+            StatusCode::BAD_REQUEST,
+            &context,
+            serde_json::json!({"error_message": "bad url"}),
+        );
+        assert_eq!(err.status(), Some(StatusCode::BAD_REQUEST));
+        assert_eq!(
+            err.to_string(),
+            "bad url (400 Bad Request from /v1/posts/add?url=https%3A%2F%2Fexample.com%2F&format=json (request abc123))",
+        );
+    }
+
+    #[test]
+    fn classify_client() {
+        let err = TestError::client(Transport {
+            source: io::ErrorKind::TimedOut.into(),
+        });
+        assert!(err.is_retryable());
+
+        let err = TestError::client(Transport {
+            source: io::ErrorKind::InvalidData.into(),
+        });
+        assert!(!err.is_retryable());
+    }
+}
@@ -5,9 +5,8 @@
 // except according to those terms.
 
 use async_trait::async_trait;
-use http::{header, Request};
 
-use crate::api::{query, ApiError, AsyncClient, AsyncQuery, Client, Endpoint, Query};
+use crate::api::{query, response, ApiError, AsyncClient, AsyncQuery, Client, Endpoint, Query};
 
 /// A query modifier that ignores the data returned from an endpoint.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,25 +25,9 @@ where
     C: Client,
 {
     fn query(&self, client: &C) -> Result<(), ApiError<C::Error>> {
-        let mut url = client.rest_endpoint(&self.endpoint.endpoint())?;
-        self.endpoint.parameters().add_to_url(&mut url);
-
-        let req = Request::builder()
-            .method(self.endpoint.method())
-            .uri(query::url_to_http_uri(url));
-        let (req, data) = if let Some((mime, data)) = self.endpoint.body()? {
-            let req = req.header(header::CONTENT_TYPE, mime);
-            (req, data)
-        } else {
-            (req, Vec::new())
-        };
-        let rsp = client.rest(req, data)?;
-        if !rsp.status().is_success() {
-            let v = serde_json::from_slice(rsp.body())?;
-            return Err(ApiError::from_pinboard(v));
-        }
-
-        Ok(())
+        query::send(&self.endpoint, client, |url, rsp| {
+            response::check_status(url, &rsp)
+        })
     }
 }
 
@@ -55,25 +38,10 @@ where
     C: AsyncClient + Sync,
 {
     async fn query_async(&self, client: &C) -> Result<(), ApiError<C::Error>> {
-        let mut url = client.rest_endpoint(&self.endpoint.endpoint())?;
-        self.endpoint.parameters().add_to_url(&mut url);
-
-        let req = Request::builder()
-            .method(self.endpoint.method())
-            .uri(query::url_to_http_uri(url));
-        let (req, data) = if let Some((mime, data)) = self.endpoint.body()? {
-            let req = req.header(header::CONTENT_TYPE, mime);
-            (req, data)
-        } else {
-            (req, Vec::new())
-        };
-        let rsp = client.rest_async(req, data).await?;
-        if !rsp.status().is_success() {
-            let v = serde_json::from_slice(rsp.body())?;
-            return Err(ApiError::from_pinboard(v));
-        }
-
-        Ok(())
+        query::send_async(&self.endpoint, client, |url, rsp| {
+            response::check_status(url, &rsp)
+        })
+        .await
     }
 }
 
@@ -124,11 +92,8 @@ mod tests {
         let client = SingleTestClient::new_raw(endpoint, "");
 
         let err = api::ignore(Dummy).query(&client).unwrap_err();
-        if let ApiError::Json { source } = err {
-            assert_eq!(
-                format!("{}", source),
-                "EOF while parsing a value at line 1 column 0",
-            );
+        if let ApiError::EmptyResponse { status, .. } = err {
+            assert_eq!(status, http::StatusCode::NOT_FOUND);
         } else {
             panic!("unexpected error: {}", err);
         }
@@ -149,7 +114,7 @@ mod tests {
         );
 
         let err = api::ignore(Dummy).query(&client).unwrap_err();
-        if let ApiError::PinboardUnrecognized { obj } = err {
+        if let ApiError::PinboardUnrecognized { obj, .. } = err {
             assert_eq!(
                 obj.pointer("/message").expect("error message"),
                 "dummy error message"
@@ -174,8 +139,7 @@ mod tests {
         );
 
         let err = api::ignore(Dummy).query(&client).unwrap_err();
-        println!("ERR: <{:?}>", err);
-        if let ApiError::PinboardUnrecognized { obj } = err {
+        if let ApiError::PinboardUnrecognized { obj, .. } = err {
             assert_eq!(
                 obj.pointer("/error").expect("error message"),
                 "dummy error message"
@@ -198,7 +162,7 @@ mod tests {
         let client = SingleTestClient::new_json(endpoint, &err_obj);
 
         let err = api::ignore(Dummy).query(&client).unwrap_err();
-        if let ApiError::PinboardUnrecognized { obj } = err {
+        if let ApiError::PinboardUnrecognized { obj, .. } = err {
             assert_eq!(obj, err_obj);
         } else {
             panic!("unexpected error: {}", err);
@@ -0,0 +1,566 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Instrumentation of the requests sent for queries
+//!
+//! With the `tracing` feature, each query runs in a `pinboard_query`
+//! span recording its `method`, endpoint `path`, `url` (with the token
+//! redacted), response `status`, `latency_ms` and `retries`. The span
+//! covers both sending the request and decoding the response.
+//!
+//! Middleware and clients which retry requests report how often they did
+//! with [`record_retries`].
+//!
+//! With the `metrics` feature, these are recorded through the `metrics`
+//! facade, labelled by the `endpoint` route: its path with any tag, bundle
+//! name or other parameter replaced by a placeholder, e.g. `/v2/tags/{tag}`.
+//!
+//! - `pinboard_requests_total`: a counter of requests by `method` and
+//!   `status` (`error` if no response arrived)
+//! - `pinboard_request_duration_seconds`: a histogram of request latency
+//! - `pinboard_errors_total`: a counter of failed queries by `class`, see
+//!   [`ApiError::class`]
+//! - `pinboard_limit_wait_seconds`: a histogram of time spent waiting to
+//!   respect the API limits, see [`record_limit_wait`]
+//!
+//! Without either feature, everything here compiles away.
+
+use std::future::Future;
+use std::time::Duration;
+
+use bytes::Bytes;
+use http::{Method, Response};
+use url::Url;
+
+use crate::api::ApiError;
+
+/// Record how many times the request being sent for a query was retried.
+///
+/// Retrying middleware, or clients which retry failed requests, should
+/// call this from `Middleware::handle` or `Client::rest` and their async
+/// counterparts, so the count shows up on the span of the query. It does
+/// nothing without the `tracing` feature.
+pub fn record_retries(retries: u32) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("retries", retries);
+    #[cfg(not(feature = "tracing"))]
+    let _ = retries;
+}
+
+/// Record time spent waiting before a request to respect the API limits.
+///
+/// Clients and queues which pace their calls by [`Limit`](crate::api::v1::Limit)
+/// should call this with the `endpoint` path they waited for. It does
+/// nothing without the `metrics` feature.
+pub fn record_limit_wait(endpoint: &str, waited: Duration) {
+    #[cfg(feature = "metrics")]
+    metrics::histogram!("pinboard_limit_wait_seconds", "endpoint" => route(endpoint))
+        .record(waited.as_secs_f64());
+    #[cfg(not(feature = "metrics"))]
+    let _ = (endpoint, waited);
+}
+
+/// Endpoint routes, first match first
+///
+/// Segments in braces match any value. Fixed paths which a route would
+/// also match come before it.
+#[cfg(feature = "metrics")]
+const ROUTES: &[&str] = &[
+    "/v1/notes/{id}/",
+    "/v2/bundles/{name}",
+    "/v2/site/tag/{tag}",
+    "/v2/tabs/{id}",
+    "/v2/tags/delete",
+    "/v2/tags/merge",
+    "/v2/tags/rename",
+    "/v2/tags/{tag}",
+];
+
+/// The route of an endpoint `path`, to label metrics without a series per
+/// tag or bundle.
+#[cfg(feature = "metrics")]
+fn route(path: &str) -> String {
+    let matches = |route: &str| {
+        let mut segments = path.split('/');
+        route.split('/').all(|pattern| {
+            segments.next().is_some_and(|segment| {
+                pattern == segment || (pattern.starts_with('{') && !segment.is_empty())
+            })
+        }) && segments.next().is_none()
+    };
+    ROUTES
+        .iter()
+        .find(|route| matches(route))
+        .map_or(path, |route| route)
+        .to_owned()
+}
+
+/// A query being sent and decoded.
+pub(crate) struct Call {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "metrics")]
+    labels: [(&'static str, String); 2],
+    #[cfg(any(feature = "metrics", feature = "tracing"))]
+    start: std::time::Instant,
+}
+
+impl Call {
+    /// Start a request to `url`.
+    pub(crate) fn start(method: &Method, url: &Url) -> Self {
+        #[cfg(not(any(feature = "metrics", feature = "tracing")))]
+        let _ = (method, url);
+        Call {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "pinboard_query",
+                method = %method,
+                path = url.path(),
+                url = %redacted(url),
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+                retries = 0u32,
+            ),
+            #[cfg(feature = "metrics")]
+            labels: [
+                ("endpoint", route(url.path())),
+                ("method", method.to_string()),
+            ],
+            #[cfg(any(feature = "metrics", feature = "tracing"))]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Run the query within the span.
+    pub(crate) fn run<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        #[cfg(feature = "tracing")]
+        return self.span.in_scope(f);
+        #[cfg(not(feature = "tracing"))]
+        f()
+    }
+
+    /// Run the query asynchronously within the span.
+    pub(crate) async fn run_async<F>(&self, f: F) -> F::Output
+    where
+        F: Future,
+    {
+        #[cfg(feature = "tracing")]
+        return tracing::Instrument::instrument(f, self.span.clone()).await;
+        #[cfg(not(feature = "tracing"))]
+        f.await
+    }
+
+    /// Record the response to the request, before it is decoded.
+    pub(crate) fn response<E>(&self, result: &Result<Response<Bytes>, ApiError<E>>)
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        #[cfg(feature = "metrics")]
+        {
+            let status = match result {
+                Ok(rsp) => rsp.status().as_str().to_owned(),
+                Err(_) => "error".into(),
+            };
+            let mut labels = self.labels.to_vec();
+            labels.push(("status", status));
+            metrics::counter!("pinboard_requests_total", &labels).increment(1);
+        }
+        #[cfg(feature = "tracing")]
+        if let Ok(rsp) = result {
+            self.span.record("status", rsp.status().as_u16());
+            tracing::debug!(status = rsp.status().as_u16(), "pinboard response");
+        }
+        #[cfg(not(any(feature = "metrics", feature = "tracing")))]
+        let _ = result;
+    }
+
+    /// Record the outcome of the query.
+    pub(crate) fn finish<T, E>(self, result: &Result<T, ApiError<E>>)
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        #[cfg(any(feature = "metrics", feature = "tracing"))]
+        let latency = self.start.elapsed();
+        #[cfg(feature = "metrics")]
+        {
+            metrics::histogram!("pinboard_request_duration_seconds", &self.labels)
+                .record(latency.as_secs_f64());
+            if let Err(err) = result {
+                metrics::counter!(
+                    "pinboard_errors_total",
+                    "endpoint" => self.labels[0].1.clone(),
+                    "class" => err.class(),
+                )
+                .increment(1);
+            }
+        }
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("latency_ms", latency.as_millis() as u64);
+            if let Err(err) = result {
+                let _guard = self.span.enter();
+                tracing::warn!(error = %err, "pinboard query failed");
+            }
+        }
+        #[cfg(not(any(feature = "metrics", feature = "tracing")))]
+        let _ = result;
+    }
+}
+
+/// The URL with the value of its token replaced.
+#[cfg(feature = "tracing")]
+fn redacted(url: &Url) -> Url {
+    let mut url = url.clone();
+    crate::auth::redact_token(&mut url, Some("REDACTED"));
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "tracing")]
+    mod spans {
+        use std::cell::Cell;
+        use std::collections::HashMap;
+        use std::fmt;
+        use std::sync::{Arc, Mutex};
+
+        use serde::de::{Deserialize, Deserializer, IgnoredAny};
+        use serde_json::json;
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+        use tracing_core::span::Current;
+        use url::Url;
+
+        use crate::api::{self, v1, AsyncQuery, Query};
+        use crate::middleware::MiddlewareClient;
+        use crate::mock::{Expectation, MockClient};
+        use crate::test::middleware::Retry;
+
+        use super::super::redacted;
+
+        type Fields = HashMap<String, String>;
+
+        /// Collects the fields of every span.
+        #[derive(Default)]
+        struct Collector {
+            spans: Arc<Mutex<Vec<(&'static str, Fields)>>>,
+            metadata: Mutex<Vec<&'static Metadata<'static>>>,
+            stack: Mutex<Vec<Id>>,
+        }
+
+        struct Visitor<'a>(&'a mut Fields);
+
+        impl Visit for Visitor<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+                self.0.insert(field.name().into(), format!("{:?}", value));
+            }
+
+            fn record_str(&mut self, field: &Field, value: &str) {
+                self.0.insert(field.name().into(), value.into());
+            }
+        }
+
+        impl Subscriber for Collector {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut fields = Fields::new();
+                span.record(&mut Visitor(&mut fields));
+                let mut spans = self.spans.lock().unwrap();
+                spans.push((span.metadata().name(), fields));
+                self.metadata.lock().unwrap().push(span.metadata());
+                Id::from_u64(spans.len() as u64)
+            }
+
+            fn record(&self, span: &Id, values: &Record<'_>) {
+                let mut spans = self.spans.lock().unwrap();
+                let (_, fields) = &mut spans[span.into_u64() as usize - 1];
+                values.record(&mut Visitor(fields));
+            }
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+            fn event(&self, _: &Event<'_>) {}
+
+            fn enter(&self, span: &Id) {
+                self.stack.lock().unwrap().push(span.clone());
+            }
+
+            fn exit(&self, _: &Id) {
+                self.stack.lock().unwrap().pop();
+            }
+
+            fn current_span(&self) -> Current {
+                match self.stack.lock().unwrap().last() {
+                    Some(id) => {
+                        let metadata = self.metadata.lock().unwrap()[id.into_u64() as usize - 1];
+                        Current::new(id.clone(), metadata)
+                    },
+                    None => Current::none(),
+                }
+            }
+        }
+
+        fn collect<F>(f: F) -> Vec<(&'static str, Fields)>
+        where
+            F: FnOnce(),
+        {
+            let collector = Collector::default();
+            let spans = Arc::clone(&collector.spans);
+            tracing::subscriber::with_default(collector, f);
+            let spans = spans.lock().unwrap();
+            spans.clone()
+        }
+
+        thread_local! {
+            static DECODED_IN: Cell<Option<&'static str>> = const { Cell::new(None) };
+        }
+
+        /// Notes the span it is decoded in.
+        struct Probe;
+
+        impl<'de> Deserialize<'de> for Probe {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let span = tracing::Span::current().metadata().map(|m| m.name());
+                DECODED_IN.with(|decoded_in| decoded_in.set(span));
+                IgnoredAny::deserialize(deserializer)?;
+                Ok(Probe)
+            }
+        }
+
+        #[test]
+        fn redacts_token() {
+            let url = Url::parse(
+                "https://api.pinboard.in/v1/tags/get?auth_token=user%3ASECRET&format=json",
+            )
+            .unwrap();
+            assert_eq!(
+                redacted(&url).as_str(),
+                "https://api.pinboard.in/v1/tags/get?auth_token=REDACTED&format=json",
+            );
+        }
+
+        #[test]
+        fn span_per_query() {
+            let mock = MockClient::ordered();
+            mock.expect(Expectation::get("v1/tags/get").respond_json(&json!({})));
+            mock.expect(
+                Expectation::get("v1/posts/update").status(http::StatusCode::TOO_MANY_REQUESTS),
+            );
+
+            let spans = collect(|| {
+                let get = v1::tags::Get::builder().build().unwrap();
+                api::ignore(get).query(&mock).unwrap();
+                let update = v1::posts::Update::builder().build().unwrap();
+                api::ignore(update).query(&mock).unwrap_err();
+            });
+
+            assert_eq!(spans.len(), 2);
+            let (name, fields) = &spans[0];
+            assert_eq!(*name, "pinboard_query");
+            assert_eq!(fields["method"], "GET");
+            assert_eq!(fields["path"], "/v1/tags/get");
+            assert_eq!(fields["status"], "200");
+            assert_eq!(fields["retries"], "0");
+            assert!(fields.contains_key("latency_ms"));
+            assert_eq!(spans[1].1["status"], "429");
+        }
+
+        #[test]
+        fn retries() {
+            let mock = MockClient::ordered();
+            mock.expect(Expectation::get("v1/tags/get").status(http::StatusCode::BAD_GATEWAY));
+            mock.expect(Expectation::get("v1/tags/get").status(http::StatusCode::BAD_GATEWAY));
+            mock.expect(Expectation::get("v1/tags/get").respond_json(&json!({})));
+            let client = MiddlewareClient::new(mock).with(Retry(3));
+
+            let spans = collect(|| {
+                let get = v1::tags::Get::builder().build().unwrap();
+                api::ignore(get).query(&client).unwrap();
+            });
+            assert_eq!(spans.len(), 1);
+            assert_eq!(spans[0].1["retries"], "2");
+            assert_eq!(spans[0].1["status"], "200");
+        }
+
+        #[test]
+        fn span_covers_decoding() {
+            let mock = MockClient::ordered();
+            mock.expect(Expectation::get("v1/tags/get").respond_json(&json!({})));
+
+            collect(|| {
+                let get = v1::tags::Get::builder().build().unwrap();
+                let _: Probe = get.query(&mock).unwrap();
+            });
+            assert_eq!(DECODED_IN.with(Cell::get), Some("pinboard_query"));
+        }
+
+        #[test]
+        fn async_span() {
+            let mock = MockClient::ordered();
+            mock.expect(Expectation::get("v1/tags/get").respond_json(&json!({})));
+
+            let spans = collect(|| {
+                let rt = tokio::runtime::Builder::new_current_thread()
+                    .build()
+                    .unwrap();
+                rt.block_on(async {
+                    let get = v1::tags::Get::builder().build().unwrap();
+                    api::ignore(get).query_async(&mock).await.unwrap();
+                });
+            });
+            assert_eq!(spans.len(), 1);
+            assert_eq!(spans[0].1["status"], "200");
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    mod recorded {
+        use std::time::Duration;
+
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+        use metrics_util::MetricKind;
+        use serde_json::json;
+
+        use crate::api::{self, v1, v2, Query};
+        use crate::mock::{Expectation, MockClient};
+
+        use super::super::route;
+
+        type Metric = (MetricKind, String, Vec<(String, String)>, DebugValue);
+
+        fn record<F>(f: F) -> Vec<Metric>
+        where
+            F: FnOnce(),
+        {
+            let recorder = DebuggingRecorder::new();
+            let snapshotter = recorder.snapshotter();
+            metrics::with_local_recorder(&recorder, f);
+            let mut metrics: Vec<_> = snapshotter
+                .snapshot()
+                .into_vec()
+                .into_iter()
+                .map(|(key, _, _, value)| {
+                    let (kind, key) = key.into_parts();
+                    let labels = key
+                        .labels()
+                        .map(|label| (label.key().into(), label.value().into()))
+                        .collect();
+                    (kind, key.name().into(), labels, value)
+                })
+                .collect();
+            metrics.sort_by(|a, b| (&a.1, &a.2).cmp(&(&b.1, &b.2)));
+            metrics
+        }
+
+        fn labels(labels: &[(&str, &str)]) -> Vec<(String, String)> {
+            labels
+                .iter()
+                .map(|(key, value)| ((*key).into(), (*value).into()))
+                .collect()
+        }
+
+        #[test]
+        fn requests_and_errors() {
+            let mock = MockClient::ordered();
+            mock.expect(Expectation::get("v1/tags/get").respond_json(&json!({})));
+            mock.expect(
+                Expectation::get("v1/tags/get").status(http::StatusCode::TOO_MANY_REQUESTS),
+            );
+
+            let metrics = record(|| {
+                let get = || v1::tags::Get::builder().build().unwrap();
+                api::ignore(get()).query(&mock).unwrap();
+                api::ignore(get()).query(&mock).unwrap_err();
+            });
+
+            let names: Vec<_> = metrics.iter().map(|m| (m.0, m.1.as_str())).collect();
+            assert_eq!(
+                names,
+                [
+                    (MetricKind::Counter, "pinboard_errors_total"),
+                    (MetricKind::Histogram, "pinboard_request_duration_seconds"),
+                    (MetricKind::Counter, "pinboard_requests_total"),
+                    (MetricKind::Counter, "pinboard_requests_total"),
+                ],
+            );
+            assert_eq!(
+                metrics[0].2,
+                labels(&[("endpoint", "/v1/tags/get"), ("class", "empty_response")]),
+            );
+            assert_eq!(metrics[0].3, DebugValue::Counter(1));
+            assert!(matches!(&metrics[1].3, DebugValue::Histogram(v) if v.len() == 2));
+            assert_eq!(
+                metrics[2].2,
+                labels(&[
+                    ("endpoint", "/v1/tags/get"),
+                    ("method", "GET"),
+                    ("status", "200"),
+                ]),
+            );
+            assert_eq!(metrics[3].2[2], ("status".into(), "429".into()));
+        }
+
+        #[test]
+        fn routes() {
+            assert_eq!(route("/v1/tags/get"), "/v1/tags/get");
+            assert_eq!(route("/v1/notes/abc123/"), "/v1/notes/{id}/");
+            assert_eq!(route("/v2/tags/to%20read"), "/v2/tags/{tag}");
+            assert_eq!(route("/v2/tags/merge"), "/v2/tags/merge");
+            assert_eq!(route("/v2/tags"), "/v2/tags");
+            assert_eq!(route("/v2/tags/"), "/v2/tags/");
+            assert_eq!(route("/v2/site/tag/rust"), "/v2/site/tag/{tag}");
+            assert_eq!(route("/v2/bundles/reading"), "/v2/bundles/{name}");
+            assert_eq!(route("/v2/tabs/42"), "/v2/tabs/{id}");
+        }
+
+        #[test]
+        fn parameters_share_a_series() {
+            let mock = MockClient::ordered();
+            for tag in ["rust", "to read"] {
+                let path = format!("v2/tags/{}", tag.replace(' ', "%20"));
+                mock.expect(Expectation::get(path).respond_json(&json!({"tag": tag, "count": 1})));
+            }
+
+            let metrics = record(|| {
+                for tag in ["rust", "to read"] {
+                    let get = v2::tags::Get::builder().tag(tag).build().unwrap();
+                    api::ignore(get).query(&mock).unwrap();
+                }
+            });
+            let requests: Vec<_> = metrics
+                .iter()
+                .filter(|m| m.1 == "pinboard_requests_total")
+                .collect();
+            assert_eq!(requests.len(), 1);
+            assert_eq!(
+                requests[0].2[0],
+                ("endpoint".into(), "/v2/tags/{tag}".into())
+            );
+            assert_eq!(requests[0].3, DebugValue::Counter(2));
+        }
+
+        #[test]
+        fn limit_wait() {
+            let metrics = record(|| {
+                api::record_limit_wait("/v1/posts/all", Duration::from_millis(1500));
+            });
+            assert_eq!(metrics.len(), 1);
+            assert_eq!(metrics[0].1, "pinboard_limit_wait_seconds");
+            assert_eq!(metrics[0].2, labels(&[("endpoint", "/v1/posts/all")]));
+            assert_eq!(metrics[0].3, DebugValue::Histogram(vec![1.5.into()]));
+        }
+    }
+}
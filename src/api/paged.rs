@@ -0,0 +1,404 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::borrow::Cow;
+
+use async_trait::async_trait;
+use http::Method;
+use serde::de::DeserializeOwned;
+
+use crate::api::{
+    query, response, ApiError, ApiVersion, AsyncClient, AsyncQuery, BodyError, Client, Endpoint,
+    Query, QueryParams,
+};
+
+/// An endpoint which returns its results in pages by offset
+///
+/// The V1 `posts/all` endpoint is not pageable: it is limited to one call
+/// every five minutes, so fetching it page by page would take far longer
+/// than a single call returning every post.
+pub trait Pageable: Endpoint {
+    /// The parameter giving the offset of the first result
+    fn start_parameter(&self) -> &'static str {
+        "start"
+    }
+
+    /// The parameter giving the number of results in a page
+    fn count_parameter(&self) -> &'static str {
+        "count"
+    }
+
+    /// The largest page the endpoint returns
+    fn max_page_size(&self) -> usize {
+        100
+    }
+
+    /// A JSON pointer to the array of results in a page
+    ///
+    /// The default, an empty pointer, is for pages which are arrays.
+    fn results_pointer(&self) -> &'static str {
+        ""
+    }
+}
+
+/// How many results to fetch from a paged endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Pagination {
+    /// Fetch every result
+    #[default]
+    All,
+    /// Fetch up to this many results
+    Limit(usize),
+}
+
+impl Pagination {
+    fn page_size(self, max: usize, fetched: usize) -> usize {
+        match self {
+            Pagination::All => max,
+            Pagination::Limit(limit) => max.min(limit.saturating_sub(fetched)),
+        }
+    }
+}
+
+/// A query modifier that fetches every page of an endpoint.
+///
+/// Pages are requested from the offset set on the endpoint, or the first
+/// result, until a short page is returned or the limit is reached; any
+/// count set on the endpoint itself is replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Paged<E> {
+    endpoint: E,
+    pagination: Pagination,
+}
+
+/// Collect the results of a paged endpoint.
+pub fn paged<E>(endpoint: E, pagination: Pagination) -> Paged<E> {
+    Paged {
+        endpoint,
+        pagination,
+    }
+}
+
+/// A single page of a paged endpoint
+struct Page<'a, E> {
+    endpoint: &'a E,
+    start: usize,
+    count: usize,
+}
+
+impl<'a, E> Endpoint for Page<'a, E>
+where
+    E: Pageable,
+{
+    fn method(&self) -> Method {
+        self.endpoint.method()
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        self.endpoint.endpoint()
+    }
+
+    fn version(&self) -> ApiVersion {
+        self.endpoint.version()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = self.endpoint.parameters();
+        params
+            .remove(self.endpoint.start_parameter())
+            .remove(self.endpoint.count_parameter())
+            .push(self.endpoint.start_parameter(), self.start as u64)
+            .push(self.endpoint.count_parameter(), self.count as u64);
+        params
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        self.endpoint.body()
+    }
+}
+
+impl<E> Paged<E>
+where
+    E: Pageable,
+{
+    /// The offset of the first result to fetch
+    fn first(&self) -> usize {
+        self.endpoint
+            .parameters()
+            .get(self.endpoint.start_parameter())
+            .and_then(|start| start.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// The next page to fetch, if any
+    fn next_page(&self, fetched: usize, last_page: Option<(usize, usize)>) -> Option<Page<'_, E>> {
+        if let Some((requested, returned)) = last_page {
+            if returned < requested {
+                return None;
+            }
+        }
+        let count = self
+            .pagination
+            .page_size(self.endpoint.max_page_size(), fetched);
+        if count == 0 {
+            return None;
+        }
+
+        Some(Page {
+            endpoint: &self.endpoint,
+            start: self.first() + fetched,
+            count,
+        })
+    }
+}
+
+impl<E, T, C> Query<Vec<T>, C> for Paged<E>
+where
+    E: Pageable,
+    T: DeserializeOwned,
+    C: Client,
+{
+    fn query(&self, client: &C) -> Result<Vec<T>, ApiError<C::Error>> {
+        let mut results = Vec::new();
+        let mut last_page = None;
+        while let Some(page) = self.next_page(results.len(), last_page) {
+            let pointer = self.endpoint.results_pointer();
+            let items: Vec<T> = query::send(&page, client, |url, rsp| {
+                response::decode_at(url, &rsp, pointer)
+            })?;
+            last_page = Some((page.count, items.len()));
+            results.extend(items);
+        }
+
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl<E, T, C> AsyncQuery<Vec<T>, C> for Paged<E>
+where
+    E: Pageable + Sync,
+    T: DeserializeOwned + Send + 'static,
+    C: AsyncClient + Sync,
+{
+    async fn query_async(&self, client: &C) -> Result<Vec<T>, ApiError<C::Error>> {
+        let mut results = Vec::new();
+        let mut last_page = None;
+        while let Some(page) = self.next_page(results.len(), last_page) {
+            let pointer = self.endpoint.results_pointer();
+            let items: Vec<T> = query::send_async(&page, client, |url, rsp| {
+                response::decode_at(url, &rsp, pointer)
+            })
+            .await?;
+            last_page = Some((page.count, items.len()));
+            results.extend(items);
+        }
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use http::request::Builder as RequestBuilder;
+    use http::Response;
+    use serde_json::json;
+    use url::Url;
+
+    use crate::api::endpoint_prelude::*;
+    use crate::api::{
+        self, ApiError, ApiVersion, AsyncClient, AsyncQuery, Pageable, Pagination, Query,
+        RestClient,
+    };
+    use crate::test::client::TestClientError;
+
+    struct Dummy;
+
+    impl Endpoint for Dummy {
+        fn method(&self) -> Method {
+            Method::GET
+        }
+
+        fn endpoint(&self) -> Cow<'static, str> {
+            "dummy".into()
+        }
+
+        fn parameters(&self) -> QueryParams<'_> {
+            let mut params = QueryParams::default();
+            params.push("q", "rust").push("count", 5u64);
+            params
+        }
+    }
+
+    impl Pageable for Dummy {
+        fn max_page_size(&self) -> usize {
+            2
+        }
+
+        fn results_pointer(&self) -> &'static str {
+            "/items"
+        }
+    }
+
+    /// A pageable endpoint with its own offset
+    struct Offset;
+
+    impl Endpoint for Offset {
+        fn method(&self) -> Method {
+            Method::GET
+        }
+
+        fn endpoint(&self) -> Cow<'static, str> {
+            "dummy".into()
+        }
+
+        fn parameters(&self) -> QueryParams<'_> {
+            let mut params = QueryParams::default();
+            params.push("start", 3u64);
+            params
+        }
+    }
+
+    impl Pageable for Offset {
+        fn max_page_size(&self) -> usize {
+            2
+        }
+
+        fn results_pointer(&self) -> &'static str {
+            "/items"
+        }
+    }
+
+    /// Serves `total` numbered items in pages, recording each request.
+    struct PagingClient {
+        total: usize,
+        requests: Mutex<Vec<String>>,
+    }
+
+    impl PagingClient {
+        fn new(total: usize) -> Self {
+            PagingClient {
+                total,
+                requests: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn serve(&self, request: RequestBuilder) -> Response<Bytes> {
+            let url = Url::parse(&request.uri_ref().unwrap().to_string()).unwrap();
+            let param = |key: &str| -> usize {
+                url.query_pairs()
+                    .find(|(k, _)| k == key)
+                    .map(|(_, v)| v.parse().unwrap())
+                    .unwrap()
+            };
+            let (start, count) = (param("start"), param("count"));
+            let items: Vec<_> = (start..self.total).take(count).collect();
+            self.requests
+                .lock()
+                .unwrap()
+                .push(url.query().unwrap().into());
+
+            let body = serde_json::to_vec(&json!({ "items": items })).unwrap();
+            Response::new(body.into())
+        }
+
+        fn requests(&self) -> Vec<String> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    impl RestClient for PagingClient {
+        type Error = TestClientError;
+
+        fn rest_endpoint(
+            &self,
+            _: ApiVersion,
+            endpoint: &str,
+        ) -> Result<Url, ApiError<Self::Error>> {
+            Ok(Url::parse("https://api.pinboard.invalid/")?.join(endpoint)?)
+        }
+    }
+
+    impl Client for PagingClient {
+        fn rest(
+            &self,
+            request: RequestBuilder,
+            _: Vec<u8>,
+        ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+            Ok(self.serve(request))
+        }
+    }
+
+    #[async_trait]
+    impl AsyncClient for PagingClient {
+        async fn rest_async(
+            &self,
+            request: RequestBuilder,
+            _: Vec<u8>,
+        ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+            Ok(self.serve(request))
+        }
+    }
+
+    #[test]
+    fn all_pages() {
+        let client = PagingClient::new(5);
+        let items: Vec<usize> = api::paged(Dummy, Pagination::All).query(&client).unwrap();
+        assert_eq!(items, [0, 1, 2, 3, 4]);
+        assert_eq!(
+            client.requests(),
+            [
+                "q=rust&start=0&count=2",
+                "q=rust&start=2&count=2",
+                "q=rust&start=4&count=2",
+            ],
+        );
+    }
+
+    #[test]
+    fn exact_pages() {
+        let client = PagingClient::new(4);
+        let items: Vec<usize> = api::paged(Dummy, Pagination::All).query(&client).unwrap();
+        assert_eq!(items, [0, 1, 2, 3]);
+        // A full last page needs an empty page to confirm the end.
+        assert_eq!(client.requests().len(), 3);
+    }
+
+    #[test]
+    fn limit() {
+        let client = PagingClient::new(10);
+        let items: Vec<usize> = api::paged(Dummy, Pagination::Limit(3))
+            .query(&client)
+            .unwrap();
+        assert_eq!(items, [0, 1, 2]);
+        assert_eq!(
+            client.requests(),
+            ["q=rust&start=0&count=2", "q=rust&start=2&count=1"],
+        );
+    }
+
+    #[test]
+    fn endpoint_start() {
+        let client = PagingClient::new(6);
+        let items: Vec<usize> = api::paged(Offset, Pagination::All).query(&client).unwrap();
+        assert_eq!(items, [3, 4, 5]);
+        assert_eq!(client.requests(), ["start=3&count=2", "start=5&count=2"],);
+    }
+
+    #[tokio::test]
+    async fn all_pages_async() {
+        let client = PagingClient::new(3);
+        let items: Vec<usize> = api::paged(Dummy, Pagination::All)
+            .query_async(&client)
+            .await
+            .unwrap();
+        assert_eq!(items, [0, 1, 2]);
+    }
+}
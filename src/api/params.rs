@@ -9,6 +9,8 @@ use std::borrow::Cow;
 use chrono::{DateTime, NaiveDate, Utc};
 use url::Url;
 
+use crate::api::BodyError;
+
 /// A trait representing a parameter value
 pub trait ParamValue<'a> {
     #[allow(clippy::wrong_self_convention)]
@@ -137,6 +139,20 @@ impl<'a> QueryParams<'a> {
         self
     }
 
+    /// The value of the first parameter named `key`.
+    pub(crate) fn get(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_ref())
+    }
+
+    /// Remove every parameter named `key`.
+    pub(crate) fn remove(&mut self, key: &str) -> &mut Self {
+        self.params.retain(|(k, _)| k != key);
+        self
+    }
+
     /// Add the parameters to a URL.
     pub fn add_to_url(&self, url: &mut Url) {
         let mut pairs = url.query_pairs_mut();
@@ -144,9 +160,63 @@ impl<'a> QueryParams<'a> {
     }
 }
 
+/// A structure for form parameters.
+#[derive(Debug, Default, Clone)]
+pub struct FormParams<'a> {
+    params: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+}
+
+impl<'a> FormParams<'a> {
+    /// Push a single parameter.
+    pub fn push<'b, K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<Cow<'a, str>>,
+        V: ParamValue<'b>,
+        'b: 'a,
+    {
+        self.params.push((key.into(), value.as_value()));
+        self
+    }
+
+    /// Push a single parameter.
+    pub fn push_opt<'b, K, V>(&mut self, key: K, value: Option<V>) -> &mut Self
+    where
+        K: Into<Cow<'a, str>>,
+        V: ParamValue<'b>,
+        'b: 'a,
+    {
+        if let Some(value) = value {
+            self.params.push((key.into(), value.as_value()));
+        }
+        self
+    }
+
+    /// Push a set of parameters.
+    pub fn extend<'b, I, K, V>(&mut self, iter: I) -> &mut Self
+    where
+        I: Iterator<Item = (K, V)>,
+        K: Into<Cow<'a, str>>,
+        V: ParamValue<'b>,
+        'b: 'a,
+    {
+        self.params
+            .extend(iter.map(|(key, value)| (key.into(), value.as_value())));
+        self
+    }
+
+    /// Encode the parameters into a request body.
+    pub fn into_body(self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let body = serde_urlencoded::to_string(self.params)?;
+        Ok(Some((
+            "application/x-www-form-urlencoded",
+            body.into_bytes(),
+        )))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::api::ParamValue;
+    use crate::api::{FormParams, ParamValue};
 
     #[test]
     fn bool_str() {
@@ -156,4 +226,14 @@ mod tests {
             assert_eq!((*i).as_value(), *s);
         }
     }
+
+    #[test]
+    fn form_params_body() {
+        let mut params = FormParams::default();
+        params.push("old", "a tag").push_opt("new", Some("b&c"));
+
+        let (mime, body) = params.into_body().unwrap().unwrap();
+        assert_eq!(mime, "application/x-www-form-urlencoded");
+        assert_eq!(body, b"old=a+tag&new=b%26c");
+    }
 }
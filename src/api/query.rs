@@ -5,17 +5,92 @@
 // except according to those terms.
 
 use async_trait::async_trait;
-use http::Uri;
+use bytes::Bytes;
+use http::request::Builder as RequestBuilder;
+use http::{header, Request, Response, Uri};
 use url::Url;
 
-use crate::api::{ApiError, AsyncClient, Client};
+use crate::api::instrument::Call;
+use crate::api::{ApiError, AsyncClient, Client, Endpoint, RestClient};
 
-pub fn url_to_http_uri(url: Url) -> Uri {
+pub fn url_to_http_uri(url: &Url) -> Uri {
     url.as_str()
         .parse::<Uri>()
         .expect("failed to parse a url::Url as an http::Uri")
 }
 
+/// Build the request for an endpoint.
+///
+/// The URL is returned as well so responses can be attributed to it.
+fn build_request<E, C>(
+    endpoint: &E,
+    client: &C,
+) -> Result<(Url, RequestBuilder, Vec<u8>), ApiError<C::Error>>
+where
+    E: Endpoint,
+    C: RestClient,
+{
+    let mut url = client.rest_endpoint(endpoint.version(), &endpoint.endpoint())?;
+    endpoint.parameters().add_to_url(&mut url);
+
+    let req = Request::builder()
+        .method(endpoint.method())
+        .uri(url_to_http_uri(&url));
+    let (req, data) = if let Some((mime, data)) = endpoint.body()? {
+        let req = req.header(header::CONTENT_TYPE, mime);
+        (req, data)
+    } else {
+        (req, Vec::new())
+    };
+
+    Ok((url, req, data))
+}
+
+/// Send the request for an endpoint and decode its response.
+///
+/// Decoding runs within the instrumentation of the request, so failures
+/// to decode are recorded along with those of the request.
+pub(crate) fn send<E, C, T, F>(endpoint: &E, client: &C, decode: F) -> Result<T, ApiError<C::Error>>
+where
+    E: Endpoint,
+    C: Client,
+    F: FnOnce(&Url, Response<Bytes>) -> Result<T, ApiError<C::Error>>,
+{
+    let (url, req, data) = build_request(endpoint, client)?;
+    let call = Call::start(&endpoint.method(), &url);
+    let result = call.run(|| {
+        let rsp = client.rest(req, data);
+        call.response(&rsp);
+        decode(&url, rsp?)
+    });
+    call.finish(&result);
+    result
+}
+
+/// Send the request for an endpoint asynchronously and decode its response.
+pub(crate) async fn send_async<E, C, T, F>(
+    endpoint: &E,
+    client: &C,
+    decode: F,
+) -> Result<T, ApiError<C::Error>>
+where
+    E: Endpoint + Sync,
+    C: AsyncClient + Sync,
+    F: FnOnce(&Url, Response<Bytes>) -> Result<T, ApiError<C::Error>>,
+{
+    let (url, req, data) = build_request(endpoint, client)?;
+    let call = Call::start(&endpoint.method(), &url);
+    let result = call
+        .run_async(async {
+            let rsp = client.rest_async(req, data).await;
+            call.response(&rsp);
+            decode(&url, rsp?)
+        })
+        .await;
+    call.finish(&result);
+    result
+}
+
 /// A trait which represents a query for a Client
 pub trait Query<T, C>
 where
@@ -5,9 +5,8 @@
 // except according to those terms.
 
 use async_trait::async_trait;
-use http::{header, Request};
 
-use crate::api::{query, ApiError, AsyncClient, AsyncQuery, Client, Endpoint, Query};
+use crate::api::{query, response, ApiError, AsyncClient, AsyncQuery, Client, Endpoint, Query};
 
 /// A query modifier that returns the raw data from the endpoint.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -26,29 +25,11 @@ where
     C: Client,
 {
     fn query(&self, client: &C) -> Result<Vec<u8>, ApiError<C::Error>> {
-        let mut url = client.rest_endpoint(&self.endpoint.endpoint())?;
-        self.endpoint.parameters().add_to_url(&mut url);
-
-        let req = Request::builder()
-            .method(self.endpoint.method())
-            .uri(query::url_to_http_uri(url));
-        let (req, data) = if let Some((mime, data)) = self.endpoint.body()? {
-            let req = req.header(header::CONTENT_TYPE, mime);
-            (req, data)
-        } else {
-            (req, Vec::new())
-        };
-        let rsp = client.rest(req, data)?;
-        if !rsp.status().is_success() {
-            let v = if let Ok(v) = serde_json::from_slice(rsp.body()) {
-                v
-            } else {
-                return Err(ApiError::server_error(rsp.status(), rsp.body()));
-            };
-            return Err(ApiError::from_pinboard(v));
-        }
+        query::send(&self.endpoint, client, |url, rsp| {
+            response::check_status(url, &rsp)?;
 
-        Ok(rsp.into_body().as_ref().into())
+            Ok(rsp.into_body().as_ref().into())
+        })
     }
 }
 
@@ -59,29 +40,12 @@ where
     C: AsyncClient + Sync,
 {
     async fn query_async(&self, client: &C) -> Result<Vec<u8>, ApiError<C::Error>> {
-        let mut url = client.rest_endpoint(&self.endpoint.endpoint())?;
-        self.endpoint.parameters().add_to_url(&mut url);
-
-        let req = Request::builder()
-            .method(self.endpoint.method())
-            .uri(query::url_to_http_uri(url));
-        let (req, data) = if let Some((mime, data)) = self.endpoint.body()? {
-            let req = req.header(header::CONTENT_TYPE, mime);
-            (req, data)
-        } else {
-            (req, Vec::new())
-        };
-        let rsp = client.rest_async(req, data).await?;
-        if !rsp.status().is_success() {
-            let v = if let Ok(v) = serde_json::from_slice(rsp.body()) {
-                v
-            } else {
-                return Err(ApiError::server_error(rsp.status(), rsp.body()));
-            };
-            return Err(ApiError::from_pinboard(v));
-        }
+        query::send_async(&self.endpoint, client, |url, rsp| {
+            response::check_status(url, &rsp)?;
 
-        Ok(rsp.into_body().as_ref().into())
+            Ok(rsp.into_body().as_ref().into())
+        })
+        .await
     }
 }
 
@@ -134,13 +98,36 @@ mod tests {
         let client = SingleTestClient::new_raw(endpoint, "");
 
         let err = api::raw(Dummy).query(&client).unwrap_err();
-        if let ApiError::PinboardService { status, .. } = err {
+        if let ApiError::EmptyResponse { status, .. } = err {
             assert_eq!(status, http::StatusCode::NOT_FOUND);
         } else {
             panic!("unexpected error: {}", err);
         }
     }
 
+    #[test]
+    fn test_pinboard_auth_failure() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("dummy")
+            .status(StatusCode::UNAUTHORIZED)
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!({
+                "error_message": "invalid token",
+            }),
+        );
+
+        let err = api::raw(Dummy).query(&client).unwrap_err();
+        assert_eq!(err.context().map(|c| c.endpoint()), Some("/dummy"));
+        if let ApiError::Auth { status, .. } = err {
+            assert_eq!(status, http::StatusCode::UNAUTHORIZED);
+        } else {
+            panic!("unexpected error: {}", err);
+        }
+    }
+
     #[test]
     fn test_pinboard_error_detection() {
         let endpoint = ExpectedUrl::builder()
@@ -156,7 +143,7 @@ mod tests {
         );
 
         let err = api::raw(Dummy).query(&client).unwrap_err();
-        if let ApiError::Pinboard { msg } = err {
+        if let ApiError::Pinboard { msg, .. } = err {
             assert_eq!(msg, "dummy error message");
         } else {
             panic!("unexpected error: {}", err);
@@ -178,7 +165,7 @@ mod tests {
         );
 
         let err = api::raw(Dummy).query(&client).unwrap_err();
-        if let ApiError::Pinboard { msg } = err {
+        if let ApiError::Pinboard { msg, .. } = err {
             assert_eq!(msg, "dummy error message");
         } else {
             panic!("unexpected error: {}", err);
@@ -198,7 +185,7 @@ mod tests {
         let client = SingleTestClient::new_json(endpoint, &err_obj);
 
         let err = api::raw(Dummy).query(&client).unwrap_err();
-        if let ApiError::PinboardUnrecognized { obj } = err {
+        if let ApiError::PinboardUnrecognized { obj, .. } = err {
             assert_eq!(obj, err_obj);
         } else {
             panic!("unexpected error: {}", err);
@@ -0,0 +1,302 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Response handling shared by all queries
+
+use std::error::Error;
+
+use bytes::Bytes;
+use http::{header, HeaderMap, Response};
+use serde::de::DeserializeOwned;
+use serde_json::error::Category;
+use url::Url;
+
+use crate::api::{ApiError, ResponseContext};
+
+/// The longest body excerpt kept in an error
+const EXCERPT_LEN: usize = 200;
+
+/// What a response body turned out to contain
+enum Body {
+    Empty,
+    Html(String),
+    Json(serde_json::Value),
+    Truncated(serde_json::Error),
+    Invalid(serde_json::Error),
+}
+
+impl Body {
+    fn classify(headers: &HeaderMap, data: &[u8]) -> Self {
+        let start = data.iter().position(|b| !b.is_ascii_whitespace());
+        let end = data.iter().rposition(|b| !b.is_ascii_whitespace());
+        let text = match (start, end) {
+            (Some(start), Some(end)) => &data[start..=end],
+            _ => &[][..],
+        };
+        if text.is_empty() {
+            return Body::Empty;
+        }
+
+        let is_html = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/html"));
+        if is_html || text.starts_with(b"<") {
+            return Body::Html(html_excerpt(text));
+        }
+
+        match serde_json::from_slice(text) {
+            Ok(value) => Body::Json(value),
+            Err(err) if err.classify() == Category::Eof => Body::Truncated(err),
+            Err(err) => Body::Invalid(err),
+        }
+    }
+
+    fn into_error<E>(self, url: &Url, rsp: &Response<Bytes>) -> ApiError<E>
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        let status = rsp.status();
+        let context = ResponseContext::new(url, rsp.headers());
+        match self {
+            Body::Empty => ApiError::EmptyResponse { status, context },
+            Body::Html(excerpt) => ApiError::HtmlResponse {
+                status,
+                excerpt,
+                context,
+            },
+            Body::Json(value) => ApiError::from_pinboard(status, &context, value),
+            Body::Truncated(source) => ApiError::TruncatedJson {
+                source,
+                status,
+                excerpt: excerpt(rsp.body()),
+                context,
+            },
+            Body::Invalid(source) => ApiError::InvalidJson {
+                source,
+                status,
+                excerpt: excerpt(rsp.body()),
+                context,
+            },
+        }
+    }
+}
+
+/// The start of a body, with whitespace collapsed
+fn excerpt(data: &[u8]) -> String {
+    let text = String::from_utf8_lossy(data);
+    let mut excerpt = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if let Some((end, _)) = excerpt.char_indices().nth(EXCERPT_LEN) {
+        excerpt.truncate(end);
+        excerpt.push('…');
+    }
+    excerpt
+}
+
+/// The title of an HTML page, or the start of it
+fn html_excerpt(data: &[u8]) -> String {
+    let text = String::from_utf8_lossy(data);
+    let lower = text.to_ascii_lowercase();
+    let title = lower.find("<title>").and_then(|start| {
+        let start = start + "<title>".len();
+        let end = start + lower[start..].find("</title>")?;
+        Some(&text[start..end])
+    });
+    match title {
+        Some(title) if !title.trim().is_empty() => excerpt(title.as_bytes()),
+        _ => excerpt(data),
+    }
+}
+
+/// Turn an unsuccessful response into an error
+pub(crate) fn check_status<E>(url: &Url, rsp: &Response<Bytes>) -> Result<(), ApiError<E>>
+where
+    E: Error + Send + Sync + 'static,
+{
+    let status = rsp.status();
+    if status.is_success() {
+        return Ok(());
+    }
+
+    let context = ResponseContext::new(url, rsp.headers());
+    if let Some(err) = ApiError::auth_failure(status, &context, rsp.body()) {
+        return Err(err);
+    }
+    Err(match Body::classify(rsp.headers(), rsp.body()) {
+        // Plain text pages keep the whole body.
+        Body::Invalid(_) => ApiError::server_error(status, &context, rsp.body()),
+        body => body.into_error(url, rsp),
+    })
+}
+
+/// Decode a successful JSON response into `T`
+pub(crate) fn decode<T, E>(url: &Url, rsp: &Response<Bytes>) -> Result<T, ApiError<E>>
+where
+    T: DeserializeOwned,
+    E: Error + Send + Sync + 'static,
+{
+    decode_at(url, rsp, "")
+}
+
+/// Decode the value at a JSON pointer of a successful response into `T`
+pub(crate) fn decode_at<T, E>(
+    url: &Url,
+    rsp: &Response<Bytes>,
+    pointer: &str,
+) -> Result<T, ApiError<E>>
+where
+    T: DeserializeOwned,
+    E: Error + Send + Sync + 'static,
+{
+    check_status(url, rsp)?;
+
+    let mut value = match Body::classify(rsp.headers(), rsp.body()) {
+        Body::Json(value) => value,
+        body => return Err(body.into_error(url, rsp)),
+    };
+    let context = || ResponseContext::new(url, rsp.headers());
+    if value.pointer("/error_message").is_some() {
+        return Err(ApiError::from_pinboard(rsp.status(), &context(), value));
+    }
+
+    let value = value
+        .pointer_mut(pointer)
+        .map(serde_json::Value::take)
+        .ok_or_else(|| {
+            let source = serde::de::Error::custom(format!("missing field `{}`", pointer));
+            ApiError::data_type::<T>(source, rsp.status(), &context())
+        })?;
+    serde_json::from_value::<T>(value)
+        .map_err(|source| ApiError::data_type::<T>(source, rsp.status(), &context()))
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+    use serde_json::json;
+
+    use crate::api::endpoint_prelude::*;
+    use crate::api::{self, ApiError, AsyncQuery, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+
+    struct Dummy;
+
+    impl Endpoint for Dummy {
+        fn method(&self) -> Method {
+            Method::GET
+        }
+
+        fn endpoint(&self) -> Cow<'static, str> {
+            "dummy".into()
+        }
+    }
+
+    fn client(status: StatusCode, body: &str) -> SingleTestClient {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("dummy")
+            .status(status)
+            .build()
+            .unwrap();
+        SingleTestClient::new_raw(endpoint, body)
+    }
+
+    #[test]
+    fn empty_body() {
+        let client = client(StatusCode::OK, "  \n");
+        let err = Query::<serde_json::Value, _>::query(&Dummy, &client).unwrap_err();
+        if let ApiError::EmptyResponse { status, .. } = err {
+            assert_eq!(status, StatusCode::OK);
+        } else {
+            panic!("unexpected error: {}", err);
+        }
+    }
+
+    #[test]
+    fn html_page() {
+        let client = client(
+            StatusCode::BAD_GATEWAY,
+            "<!DOCTYPE html>\n<html><head><title>502 Bad\n  Gateway</title></head></html>",
+        );
+        let err = api::raw(Dummy).query(&client).unwrap_err();
+        assert!(err.is_retryable());
+        if let ApiError::HtmlResponse { excerpt, .. } = err {
+            assert_eq!(excerpt, "502 Bad Gateway");
+        } else {
+            panic!("unexpected error: {}", err);
+        }
+    }
+
+    #[test]
+    fn truncated_json() {
+        let client = client(StatusCode::OK, r#"{"update_time": "2024-"#);
+        let err = Query::<serde_json::Value, _>::query(&Dummy, &client).unwrap_err();
+        assert!(err.is_retryable());
+        if let ApiError::TruncatedJson { excerpt, .. } = err {
+            assert_eq!(excerpt, r#"{"update_time": "2024-"#);
+        } else {
+            panic!("unexpected error: {}", err);
+        }
+    }
+
+    #[test]
+    fn truncated_json_client_error() {
+        let client = client(StatusCode::BAD_REQUEST, r#"{"error_message": "bad"#);
+        let err = Query::<serde_json::Value, _>::query(&Dummy, &client).unwrap_err();
+        assert!(matches!(err, ApiError::TruncatedJson { .. }));
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn invalid_json() {
+        let client = client(StatusCode::OK, "update_time: never");
+        let err = Query::<serde_json::Value, _>::query(&Dummy, &client).unwrap_err();
+        if let ApiError::InvalidJson { excerpt, .. } = err {
+            assert_eq!(excerpt, "update_time: never");
+        } else {
+            panic!("unexpected error: {}", err);
+        }
+    }
+
+    #[test]
+    fn plain_text_error() {
+        let client = client(StatusCode::TOO_MANY_REQUESTS, "Too Many Requests");
+        let err = api::ignore(Dummy).query(&client).unwrap_err();
+        assert!(err.is_rate_limited());
+        if let ApiError::PinboardService { data, .. } = err {
+            assert_eq!(data, b"Too Many Requests");
+        } else {
+            panic!("unexpected error: {}", err);
+        }
+    }
+
+    #[tokio::test]
+    async fn error_object_on_success() {
+        let endpoint = ExpectedUrl::builder().endpoint("dummy").build().unwrap();
+        let client = SingleTestClient::new_json(endpoint, &json!({"error_message": "bad"}));
+        let err = AsyncQuery::<serde_json::Value, _>::query_async(&Dummy, &client)
+            .await
+            .unwrap_err();
+        if let ApiError::Pinboard { msg, status, .. } = err {
+            assert_eq!(msg, "bad");
+            assert_eq!(status, StatusCode::OK);
+        } else {
+            panic!("unexpected error: {}", err);
+        }
+    }
+
+    #[test]
+    fn long_excerpt() {
+        let body = format!("[{}", "1,".repeat(300));
+        let client = client(StatusCode::OK, &body);
+        let err = Query::<serde_json::Value, _>::query(&Dummy, &client).unwrap_err();
+        if let ApiError::TruncatedJson { excerpt, .. } = err {
+            assert_eq!(excerpt.chars().count(), 201);
+            assert!(excerpt.ends_with('…'));
+        } else {
+            panic!("unexpected error: {}", err);
+        }
+    }
+}
@@ -110,7 +110,7 @@ impl<'a> Endpoint for Add<'a> {
         "v1/posts/add".into()
     }
 
-    fn parameters(&self) -> QueryParams {
+    fn parameters(&self) -> QueryParams<'_> {
         let mut params = QueryParams::default();
 
         params
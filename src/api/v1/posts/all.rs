@@ -86,7 +86,7 @@ impl<'a> Endpoint for All<'a> {
         "v1/posts/all".into()
     }
 
-    fn parameters(&self) -> QueryParams {
+    fn parameters(&self) -> QueryParams<'_> {
         let mut params = QueryParams::default();
 
         params
@@ -67,7 +67,7 @@ impl<'a> Endpoint for Dates<'a> {
         "v1/posts/dates".into()
     }
 
-    fn parameters(&self) -> QueryParams {
+    fn parameters(&self) -> QueryParams<'_> {
         let mut params = QueryParams::default();
 
         params.push_opt("tag", self.tags.as_ref().map(|xs| xs.join(" ")));
@@ -50,7 +50,7 @@ impl Endpoint for Delete {
         "v1/posts/delete".into()
     }
 
-    fn parameters(&self) -> QueryParams {
+    fn parameters(&self) -> QueryParams<'_> {
         let mut params = QueryParams::default();
 
         params.push("url", self.url.as_ref());
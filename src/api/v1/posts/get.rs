@@ -66,7 +66,7 @@ impl<'a> Endpoint for Get<'a> {
         "v1/posts/get".into()
     }
 
-    fn parameters(&self) -> QueryParams {
+    fn parameters(&self) -> QueryParams<'_> {
         let mut params = QueryParams::default();
 
         params
@@ -77,7 +77,7 @@ impl<'a> Endpoint for Recent<'a> {
         "v1/posts/recent".into()
     }
 
-    fn parameters(&self) -> QueryParams {
+    fn parameters(&self) -> QueryParams<'_> {
         let mut params = QueryParams::default();
 
         params
@@ -51,7 +51,7 @@ impl Endpoint for Suggest {
         "v1/posts/suggest".into()
     }
 
-    fn parameters(&self) -> QueryParams {
+    fn parameters(&self) -> QueryParams<'_> {
         let mut params = QueryParams::default();
 
         params.push("url", self.url.as_ref());
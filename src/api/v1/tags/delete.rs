@@ -48,7 +48,7 @@ impl<'a> Endpoint for Delete<'a> {
         "v1/tags/delete".into()
     }
 
-    fn parameters(&self) -> QueryParams {
+    fn parameters(&self) -> QueryParams<'_> {
         let mut params = QueryParams::default();
 
         params.push("tag", self.tag.as_ref());
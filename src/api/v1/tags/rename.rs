@@ -52,7 +52,7 @@ impl<'a> Endpoint for Rename<'a> {
         "v1/tags/rename".into()
     }
 
-    fn parameters(&self) -> QueryParams {
+    fn parameters(&self) -> QueryParams<'_> {
         let mut params = QueryParams::default();
 
         params
@@ -15,7 +15,34 @@ pub mod notes;
 pub mod search;
 pub mod site;
 pub mod subscriptions;
+pub mod tabs;
 pub mod tags;
 pub mod test;
 pub mod url;
 pub mod user;
+
+// Check that a value can be used as a single segment of an endpoint path.
+// `.` and `..` would be resolved as relative segments when the URL is
+// parsed, even when percent-encoded, so they are rejected.
+pub(crate) fn check_path_segment(field: &str, value: &str) -> Result<(), String> {
+    if value == "." || value == ".." {
+        return Err(format!(
+            "Endpoint does not accept `{}` as `{}`",
+            value, field
+        ));
+    }
+    Ok(())
+}
+
+// Percent-encode a value for use as a single segment of an endpoint path
+pub(crate) fn path_segment(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            },
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
@@ -5,3 +5,18 @@
 // except according to those terms.
 
 //! Builders to interact with the set of bundles endpoints.
+//!
+//! A bundle is a named set of tags.
+mod create;
+mod delete;
+mod list;
+mod update;
+
+#[doc(inline)]
+pub use self::create::Create;
+#[doc(inline)]
+pub use self::delete::Delete;
+#[doc(inline)]
+pub use self::list::List;
+#[doc(inline)]
+pub use self::update::Update;
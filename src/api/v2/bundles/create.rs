@@ -0,0 +1,133 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create a Create endpoint for bundles.
+///
+/// # Arguments
+/// There are two arguments for this builder.
+/// * `name` - name of the bundle
+/// * `tags` - the tags in the bundle (at least one)
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::bundles::Create;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let create_endpoint = Create::builder()
+///                       .name("languages")
+///                       .tags(vec!["rust", "go"])
+///                       .build().unwrap();
+/// assert_eq!(create_endpoint.endpoint(), "bundles");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct Create<'a> {
+    /// The bundle name
+    #[builder(setter(into))]
+    name: Cow<'a, str>,
+    /// The tags in the bundle
+    #[builder(setter(into))]
+    tags: Cow<'a, [&'a str]>,
+}
+
+impl<'a> CreateBuilder<'a> {
+    // Ensure the bundle has at least one tag
+    fn validate(&self) -> Result<(), String> {
+        if let Some(ref xs) = self.tags {
+            if xs.is_empty() {
+                return Err("Endpoint requires at least 1 tag in a bundle".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Create<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> CreateBuilder<'a> {
+        CreateBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Create<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "bundles".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+
+        params
+            .push("name", self.name.as_ref())
+            .push("tags", self.tags.join(" "));
+        params.into_body()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+
+    use crate::api::v2::bundles::Create;
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+
+    #[test]
+    fn name_is_required() {
+        let err = Create::builder().tags(vec!["rust"]).build().unwrap_err();
+        assert_eq!(&err.to_string(), "`name` must be initialized")
+    }
+
+    #[test]
+    fn tags_is_required() {
+        let err = Create::builder().name("languages").build().unwrap_err();
+        assert_eq!(&err.to_string(), "`tags` must be initialized")
+    }
+
+    #[test]
+    fn tags_is_not_empty() {
+        let err = Create::builder()
+            .name("languages")
+            .tags(vec![])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint requires at least 1 tag in a bundle"
+        )
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/bundles")
+            .content_type("application/x-www-form-urlencoded")
+            .body_str("name=languages&tags=rust+go")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Create::builder()
+            .name("languages")
+            .tags(vec!["rust", "go"])
+            .build()
+            .unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+}
@@ -0,0 +1,98 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use crate::api::v2::{check_path_segment, path_segment};
+use derive_builder::Builder;
+
+/// Create a Delete endpoint for bundles.
+///
+/// Only the bundle is removed; its tags are left on their bookmarks.
+///
+/// # Arguments
+/// * `name` - name of the bundle
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::bundles::Delete;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let del_endpoint = Delete::builder().name("languages").build().unwrap();
+/// assert_eq!(del_endpoint.endpoint(), "bundles/languages");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct Delete<'a> {
+    /// The bundle name
+    #[builder(setter(into))]
+    name: Cow<'a, str>,
+}
+
+impl<'a> DeleteBuilder<'a> {
+    // The name must be usable as a path segment
+    fn validate(&self) -> Result<(), String> {
+        match self.name {
+            Some(ref name) => check_path_segment("name", name),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Delete<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> DeleteBuilder<'a> {
+        DeleteBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Delete<'a> {
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("bundles/{}", path_segment(&self.name)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+
+    use crate::api::v2::bundles::Delete;
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+
+    #[test]
+    fn name_is_required() {
+        let err = Delete::builder().build().unwrap_err();
+        assert_eq!(&err.to_string(), "`name` must be initialized")
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::DELETE)
+            .endpoint("v2/bundles/reading%20list")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Delete::builder().name("reading list").build().unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn dot_segments_are_rejected() {
+        let err = Delete::builder().name("..").build().unwrap_err();
+        assert_eq!(&err.to_string(), "Endpoint does not accept `..` as `name`");
+    }
+}
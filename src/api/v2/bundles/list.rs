@@ -0,0 +1,64 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create a List endpoint for bundles.
+///
+/// There are no arguments for this endpoint.
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::bundles::List;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let list_endpoint = List::builder().build().unwrap();
+/// assert_eq!(list_endpoint.endpoint(), "bundles");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Builder)]
+pub struct List {}
+
+impl List {
+    /// Create a builder for the endpoint
+    pub fn builder() -> ListBuilder {
+        ListBuilder::default()
+    }
+}
+
+impl Endpoint for List {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "bundles".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::v2::bundles::List;
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/bundles")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = List::builder().build().unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+}
@@ -0,0 +1,180 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use crate::api::v2::{check_path_segment, path_segment};
+use derive_builder::Builder;
+
+/// Create an Update endpoint for bundles.
+///
+/// # Arguments
+/// This builder requires the name of the bundle to update.
+/// * `name` - name of the bundle
+///
+/// At least one of the two optional arguments must be given.
+/// * `rename` - a new name for the bundle
+/// * `tags` - replace the tags in the bundle
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::bundles::Update;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let update_endpoint = Update::builder()
+///                       .name("languages")
+///                       .tags(vec!["rust", "go", "zig"])
+///                       .build().unwrap();
+/// assert_eq!(update_endpoint.endpoint(), "bundles/languages");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
+pub struct Update<'a> {
+    /// The bundle name
+    #[builder(setter(into))]
+    name: Cow<'a, str>,
+    /// The new bundle name
+    #[builder(setter(into), default)]
+    rename: Option<Cow<'a, str>>,
+    /// The new tags for the bundle
+    #[builder(setter(into), default)]
+    tags: Option<Cow<'a, [&'a str]>>,
+}
+
+impl<'a> UpdateBuilder<'a> {
+    // Ensure the name is usable as a path segment, that there is something
+    // to update and that the bundle keeps a tag
+    fn validate(&self) -> Result<(), String> {
+        if let Some(ref name) = self.name {
+            check_path_segment("name", name)?;
+        }
+        let rename = matches!(self.rename, Some(Some(_)));
+        match self.tags {
+            Some(Some(ref xs)) if xs.is_empty() => {
+                Err("Endpoint requires at least 1 tag in a bundle".to_string())
+            },
+            Some(Some(_)) => Ok(()),
+            _ if rename => Ok(()),
+            _ => Err("Endpoint requires `rename` or `tags` to update".to_string()),
+        }
+    }
+}
+
+impl<'a> Update<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> UpdateBuilder<'a> {
+        UpdateBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Update<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("bundles/{}", path_segment(&self.name)).into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+
+        params
+            .push_opt("name", self.rename.as_ref())
+            .push_opt("tags", self.tags.as_ref().map(|xs| xs.join(" ")));
+        params.into_body()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+
+    use crate::api::v2::bundles::Update;
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+
+    #[test]
+    fn name_is_required() {
+        let err = Update::builder().rename("langs").build().unwrap_err();
+        assert_eq!(&err.to_string(), "`name` must be initialized")
+    }
+
+    #[test]
+    fn change_is_required() {
+        let err = Update::builder().name("languages").build().unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint requires `rename` or `tags` to update"
+        )
+    }
+
+    #[test]
+    fn tags_is_not_empty() {
+        let err = Update::builder()
+            .name("languages")
+            .rename("langs")
+            .tags(vec![])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint requires at least 1 tag in a bundle"
+        )
+    }
+
+    #[test]
+    fn endpoint_rename() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/bundles/languages")
+            .content_type("application/x-www-form-urlencoded")
+            .body_str("name=langs")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Update::builder()
+            .name("languages")
+            .rename("langs")
+            .build()
+            .unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn endpoint_tags() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/bundles/languages")
+            .content_type("application/x-www-form-urlencoded")
+            .body_str("tags=rust+zig")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Update::builder()
+            .name("languages")
+            .tags(vec!["rust", "zig"])
+            .build()
+            .unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn dot_segments_are_rejected() {
+        let err = Update::builder()
+            .name("..")
+            .rename("languages")
+            .build()
+            .unwrap_err();
+        assert_eq!(&err.to_string(), "Endpoint does not accept `..` as `name`");
+    }
+}
@@ -3,5 +3,17 @@
 // <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
+#![allow(clippy::module_inception)]
 
 //! Builders to interact with the set of delta endpoints.
+//!
+//! The delta endpoint returns the changes made to bookmarks since a
+//! cursor returned by a previous call. [`DeltaCursor`] keeps track of
+//! the cursor between calls.
+mod cursor;
+mod delta;
+
+#[doc(inline)]
+pub use self::cursor::DeltaCursor;
+#[doc(inline)]
+pub use self::delta::Delta;
@@ -0,0 +1,144 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::v2::delta::Delta;
+use crate::api::{ApiError, AsyncClient, AsyncQuery, Client, Query};
+use crate::types::v2::DeltaPage;
+
+/// Tracks the cursor of the delta endpoint between calls.
+///
+/// Each fetch requests the changes since the last cursor seen and
+/// advances to the cursor returned with them. The cursor can be saved
+/// with [`DeltaCursor::cursor`] and restored with
+/// [`DeltaCursor::from_cursor`] so a sync job can resume where it left off.
+///
+/// # Example
+/// ```rust,no_run
+/// # #[cfg(feature = "reqwest")]
+/// # fn main() {
+/// # use pinboard_rs::api::v2::delta::DeltaCursor;
+/// # use pinboard_rs::Pinboard;
+/// let pb = Pinboard::new("api.pinboard.in", "<TOKEN>").unwrap();
+/// let mut cursor = DeltaCursor::new();
+/// loop {
+///     let page = cursor.fetch(&pb).unwrap();
+///     println!("{} changes", page.events.len());
+///     if !page.more {
+///         break;
+///     }
+/// }
+/// println!("resume from {:?}", cursor.cursor());
+/// # }
+/// # #[cfg(not(feature = "reqwest"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeltaCursor {
+    cursor: Option<String>,
+}
+
+impl DeltaCursor {
+    /// Start from the beginning of the change feed
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resume from a previously returned cursor
+    pub fn from_cursor<S>(cursor: S) -> Self
+    where
+        S: Into<String>,
+    {
+        DeltaCursor {
+            cursor: Some(cursor.into()),
+        }
+    }
+
+    /// The current cursor, if any page has been seen
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+
+    /// The endpoint for the changes since the current cursor
+    pub fn endpoint(&self) -> Delta<'_> {
+        let mut builder = Delta::builder();
+        if let Some(cursor) = self.cursor.as_deref() {
+            builder.since(cursor);
+        }
+        builder
+            .build()
+            .expect("a delta endpoint without `count` is always valid")
+    }
+
+    /// Advance the cursor past a page of changes
+    pub fn advance(&mut self, page: &DeltaPage) {
+        self.cursor = Some(page.cursor.clone());
+    }
+
+    /// Fetch the next page of changes and advance the cursor
+    pub fn fetch<C>(&mut self, client: &C) -> Result<DeltaPage, ApiError<C::Error>>
+    where
+        C: Client,
+    {
+        let page: DeltaPage = self.endpoint().query(client)?;
+        self.advance(&page);
+        Ok(page)
+    }
+
+    /// Fetch the next page of changes asynchronously and advance the cursor
+    pub async fn fetch_async<C>(&mut self, client: &C) -> Result<DeltaPage, ApiError<C::Error>>
+    where
+        C: AsyncClient + Sync,
+    {
+        let page: DeltaPage = self.endpoint().query_async(client).await?;
+        self.advance(&page);
+        Ok(page)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::api::v2::delta::DeltaCursor;
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::DeltaEvent;
+
+    fn page() -> serde_json::Value {
+        json!({
+            "cursor": "c-2",
+            "more": false,
+            "events": [
+                {"type": "delete", "id": "abc", "url": "https://example.com"},
+            ],
+        })
+    }
+
+    #[test]
+    fn fetch_advances() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/delta")
+            .add_query_params(&[("since", "c-1")])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(endpoint, &page());
+
+        let mut cursor = DeltaCursor::from_cursor("c-1");
+        let page = cursor.fetch(&client).unwrap();
+        assert_eq!(cursor.cursor(), Some("c-2"));
+        assert!(matches!(page.events[0], DeltaEvent::Delete { .. }));
+    }
+
+    #[tokio::test]
+    async fn fetch_async_advances() {
+        let endpoint = ExpectedUrl::builder().endpoint("v2/delta").build().unwrap();
+        let client = SingleTestClient::new_json(endpoint, &page());
+
+        let mut cursor = DeltaCursor::new();
+        assert_eq!(cursor.cursor(), None);
+        cursor.fetch_async(&client).await.unwrap();
+        assert_eq!(cursor.cursor(), Some("c-2"));
+    }
+}
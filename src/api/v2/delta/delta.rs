@@ -0,0 +1,116 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create a Delta endpoint for bookmark changes.
+///
+/// # Arguments
+/// This builder takes two optional arguments.
+/// * `since` - cursor returned by a previous call (all changes when omitted)
+/// * `count` - maximum number of events to return (up to 1000)
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::delta::Delta;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let delta_endpoint = Delta::builder().since("c-1234").build().unwrap();
+/// assert_eq!(delta_endpoint.endpoint(), "delta");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
+pub struct Delta<'a> {
+    /// Cursor from a previous call
+    #[builder(setter(into), default)]
+    since: Option<Cow<'a, str>>,
+    /// Maximum number of events to return
+    #[builder(default)]
+    count: Option<u64>,
+}
+
+impl<'a> DeltaBuilder<'a> {
+    // Count is limited to 1000.
+    fn validate(&self) -> Result<(), String> {
+        if let Some(Some(count)) = self.count {
+            if count > 1000 {
+                return Err("Endpoint only accepts `count` of 1000 or less".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Delta<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> DeltaBuilder<'a> {
+        DeltaBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Delta<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "delta".into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+
+        params
+            .push_opt("since", self.since.as_ref())
+            .push_opt("count", self.count);
+
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::v2::delta::Delta;
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder().endpoint("v2/delta").build().unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Delta::builder().build().unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn endpoint_since() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/delta")
+            .add_query_params(&[("since", "c-1234"), ("count", "50")])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Delta::builder().since("c-1234").count(50).build().unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn endpoint_count_1001() {
+        let err = Delta::builder().count(1001).build().unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint only accepts `count` of 1000 or less"
+        )
+    }
+}
@@ -4,27 +4,128 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::fmt::{self, Debug};
+
 use derive_builder::Builder;
+use serde::Serialize;
 
 use crate::api::endpoint_prelude::*;
 
-/// Posts to the `awesome` endpoint.
-#[derive(Debug, Clone, Copy, Builder)]
-pub struct Auth {}
+/// Posts credentials to the `auth` endpoint.
+///
+/// Returns an API token for the account. The credentials are sent as a
+/// JSON body.
+///
+/// # Arguments
+/// There are two arguments for this builder.
+/// * `username` - the account name
+/// * `password` - the account password
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::general::Auth;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let auth_endpoint = Auth::builder().username("user").password("secret").build().unwrap();
+/// assert_eq!(auth_endpoint.endpoint(), "auth");
+/// # }
+/// ```
+#[derive(Clone, Builder, Serialize)]
+pub struct Auth<'a> {
+    /// The account name
+    #[builder(setter(into))]
+    username: Cow<'a, str>,
+    /// The account password
+    #[builder(setter(into))]
+    password: Cow<'a, str>,
+}
+
+impl<'a> Debug for Auth<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Auth")
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
 
-impl Auth {
+impl<'a> Auth<'a> {
     /// Create a builder for the endpoint.
-    pub fn builder() -> AuthBuilder {
+    pub fn builder() -> AuthBuilder<'a> {
         AuthBuilder::default()
     }
 }
 
-impl Endpoint for Auth {
+impl<'a> Endpoint for Auth<'a> {
     fn method(&self) -> Method {
         Method::POST
     }
 
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
     fn endpoint(&self) -> Cow<'static, str> {
         "auth".into()
     }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(self)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+    use serde_json::json;
+
+    use crate::api::v2::general::Auth;
+    use crate::api::Query;
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::AuthToken;
+
+    #[test]
+    fn username_is_required() {
+        let err = Auth::builder().password("secret").build().unwrap_err();
+        assert_eq!(&err.to_string(), "`username` must be initialized")
+    }
+
+    #[test]
+    fn password_is_required() {
+        let err = Auth::builder().username("user").build().unwrap_err();
+        assert_eq!(&err.to_string(), "`password` must be initialized")
+    }
+
+    #[test]
+    fn password_is_not_debugged() {
+        let endpoint = Auth::builder()
+            .username("user")
+            .password("secret")
+            .build()
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", endpoint),
+            r#"Auth { username: "user", .. }"#
+        );
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/auth")
+            .content_type("application/json")
+            .body_str(r#"{"username":"user","password":"secret"}"#)
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(endpoint, &json!({"token": "user:ABC123"}));
+
+        let token: AuthToken = Auth::builder()
+            .username("user")
+            .password("secret")
+            .build()
+            .unwrap()
+            .query(&client)
+            .unwrap();
+        assert_eq!(token.token, "user:ABC123");
+    }
 }
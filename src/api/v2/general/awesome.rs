@@ -24,7 +24,35 @@ impl Endpoint for Awesome {
         Method::POST
     }
 
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
     fn endpoint(&self) -> Cow<'static, str> {
-        "hello".into()
+        "awesome".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+    use serde_json::json;
+
+    use crate::api::v2::general::Awesome;
+    use crate::api::Query;
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::Awesome as AwesomeResponse;
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/awesome")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(endpoint, &json!({"awesome": true}));
+
+        let rsp: AwesomeResponse = Awesome::builder().build().unwrap().query(&client).unwrap();
+        assert!(rsp.awesome);
     }
 }
@@ -24,7 +24,30 @@ impl Endpoint for Hello {
         Method::GET
     }
 
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
     fn endpoint(&self) -> Cow<'static, str> {
         "hello".into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::api::v2::general::Hello;
+    use crate::api::Query;
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::Hello as HelloResponse;
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder().endpoint("v2/hello").build().unwrap();
+        let client = SingleTestClient::new_json(endpoint, &json!({"message": "hello"}));
+
+        let rsp: HelloResponse = Hello::builder().build().unwrap().query(&client).unwrap();
+        assert_eq!(rsp.message, "hello");
+    }
+}
@@ -8,7 +8,10 @@ use derive_builder::Builder;
 
 use crate::api::endpoint_prelude::*;
 
-/// Posts to the `awesome` endpoint.
+/// Query the `last_update` endpoint.
+///
+/// Returns the time of the most recent change to the bookmarks, as a
+/// [`crate::types::Update`].
 #[derive(Debug, Clone, Copy, Builder)]
 pub struct LastUpdate {}
 
@@ -24,7 +27,42 @@ impl Endpoint for LastUpdate {
         Method::GET
     }
 
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
     fn endpoint(&self) -> Cow<'static, str> {
         "last_update".into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use serde_json::json;
+
+    use crate::api::v2::general::LastUpdate;
+    use crate::api::Query;
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::Update;
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/last_update")
+            .build()
+            .unwrap();
+        let client =
+            SingleTestClient::new_json(endpoint, &json!({"update_time": "2024-10-27T17:38:11Z"}));
+
+        let rsp: Update = LastUpdate::builder()
+            .build()
+            .unwrap()
+            .query(&client)
+            .unwrap();
+        assert_eq!(
+            rsp.update_time,
+            DateTime::parse_from_rfc3339("2024-10-27T17:38:11Z").unwrap()
+        );
+    }
+}
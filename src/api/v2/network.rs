@@ -5,3 +5,10 @@
 // except according to those terms.
 
 //! Builders to interact with the set of network endpoints.
+//!
+//! The network is the set of public bookmarks from the users and tags
+//! the account is subscribed to.
+mod feed;
+
+#[doc(inline)]
+pub use self::feed::Feed;
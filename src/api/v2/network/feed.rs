@@ -0,0 +1,172 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create a Feed endpoint for the network.
+///
+/// # Arguments
+/// This builder takes two optional arguments.
+/// * `start` - offset value
+/// * `count` - number of results to return (up to 100)
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::network::Feed;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let feed_endpoint = Feed::builder().count(25).build().unwrap();
+/// assert_eq!(feed_endpoint.endpoint(), "network");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Builder)]
+#[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
+pub struct Feed {
+    /// Offset value (default is 0)
+    #[builder(default)]
+    start: Option<u64>,
+    /// Number of results to return
+    #[builder(default)]
+    count: Option<u8>,
+}
+
+impl FeedBuilder {
+    // Count is limited to 100.
+    fn validate(&self) -> Result<(), String> {
+        if let Some(Some(count)) = self.count {
+            if count > 100 {
+                return Err("Endpoint only accepts `count` of 100 or less".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Feed {
+    /// Create a builder for the endpoint
+    pub fn builder() -> FeedBuilder {
+        FeedBuilder::default()
+    }
+}
+
+impl Endpoint for Feed {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "network".into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+
+        params
+            .push_opt("start", self.start)
+            .push_opt("count", self.count);
+
+        params
+    }
+}
+
+impl Pageable for Feed {}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::api::v2::network::Feed;
+    use crate::api::{self, Pagination, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::PublicBookmarks;
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/network")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Feed::builder().build().unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn endpoint_count() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/network")
+            .add_query_params(&[("start", "10"), ("count", "20")])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Feed::builder().start(10).count(20).build().unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn endpoint_count_101() {
+        let err = Feed::builder().count(101).build().unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint only accepts `count` of 100 or less"
+        )
+    }
+
+    #[test]
+    fn decode() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/network")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!([{
+                "user": "someone",
+                "url": "https://example.com",
+                "title": "Example",
+                "description": "",
+                "tags": ["one"],
+                "created_at": "2024-01-01T00:00:00Z",
+            }]),
+        );
+
+        let feed: PublicBookmarks = Feed::builder().build().unwrap().query(&client).unwrap();
+        assert_eq!(feed[0].user, "someone");
+    }
+
+    #[test]
+    fn paged() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/network")
+            .add_query_params(&[("start", "10"), ("count", "1")])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!([{
+                "user": "someone",
+                "url": "https://example.com/",
+                "title": "Example",
+                "description": "",
+                "tags": [],
+                "created_at": "2024-01-01T00:00:00Z",
+            }]),
+        );
+
+        let endpoint = Feed::builder().start(10).build().unwrap();
+        let feed: PublicBookmarks = api::paged(endpoint, Pagination::Limit(1))
+            .query(&client)
+            .unwrap();
+        assert_eq!(feed[0].user, "someone");
+    }
+}
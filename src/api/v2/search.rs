@@ -3,5 +3,10 @@
 // <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
+#![allow(clippy::module_inception)]
 
-//! Builders to interact with the set of serach endpoints.
+//! Builders to interact with the set of search endpoints.
+mod search;
+
+#[doc(inline)]
+pub use self::search::{Search, Visibility};
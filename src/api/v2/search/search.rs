@@ -0,0 +1,434 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use chrono::NaiveDate;
+use derive_builder::Builder;
+
+use crate::api::endpoint_prelude::*;
+
+/// Restrict a search to public or private bookmarks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    /// Only public bookmarks
+    Public,
+    /// Only private bookmarks
+    Private,
+}
+
+/// Create a Search endpoint for bookmarks.
+///
+/// The arguments are combined into a single search query `q`, e.g.
+/// `rust "error handling" tag:programming after:2021-01-01 is:unread`.
+///
+/// # Arguments
+/// This builder takes six optional arguments, at least one of which
+/// must be given.
+/// * `terms` - full-text terms; terms containing spaces or `:` are quoted, so
+///   they are searched as phrases rather than operators, and terms may not be
+///   blank or contain `"`
+/// * `tags` - only bookmarks carrying all of these tags; tags may not be empty
+///   or contain whitespace or `"`
+/// * `after` - only bookmarks created on or after this day
+/// * `before` - only bookmarks created on or before this day
+/// * `visibility` - only public or only private bookmarks
+/// * `unread` - only bookmarks marked as unread
+///
+/// The results are paginated with two further optional arguments.
+/// * `start` - offset value
+/// * `count` - number of results to return (up to 100)
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::search::Search;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let search_endpoint = Search::builder()
+///                       .terms(vec!["rust", "error handling"])
+///                       .tags(vec!["programming"])
+///                       .unread(true)
+///                       .build().unwrap();
+/// assert_eq!(search_endpoint.endpoint(), "search");
+/// assert_eq!(
+///     search_endpoint.search_query(),
+///     r#"rust "error handling" tag:programming is:unread"#
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
+pub struct Search<'a> {
+    /// Full-text search terms
+    #[builder(setter(into), default)]
+    terms: Option<Cow<'a, [&'a str]>>,
+    /// Tag filter
+    #[builder(setter(into), default)]
+    tags: Option<Cow<'a, [&'a str]>>,
+    /// Return only bookmarks created on or after this day
+    #[builder(default)]
+    after: Option<NaiveDate>,
+    /// Return only bookmarks created on or before this day
+    #[builder(default)]
+    before: Option<NaiveDate>,
+    /// Return only public or private bookmarks
+    #[builder(default)]
+    visibility: Option<Visibility>,
+    /// Return only unread bookmarks
+    #[builder(default)]
+    unread: Option<bool>,
+    /// Offset value (default is 0)
+    #[builder(default)]
+    start: Option<u64>,
+    /// Number of results to return
+    #[builder(default)]
+    count: Option<u8>,
+}
+
+impl<'a> SearchBuilder<'a> {
+    // Check that there is something to search for, that terms and tags
+    // can be written into the query, that the date range is in order, and
+    // that count does not exceed 100.
+    fn validate(&self) -> Result<(), String> {
+        let terms = match self.terms {
+            Some(Some(ref xs)) => xs.as_ref(),
+            _ => &[],
+        };
+        if terms.iter().any(|term| term.trim().is_empty()) {
+            return Err("Endpoint does not accept a blank search term".to_string());
+        }
+        if let Some(term) = terms.iter().find(|term| term.contains('"')) {
+            return Err(format!(
+                "Endpoint does not accept `\"` in search term `{}`",
+                term
+            ));
+        }
+        let tags = match self.tags {
+            Some(Some(ref xs)) => xs.as_ref(),
+            _ => &[],
+        };
+        if tags.iter().any(|tag| tag.is_empty()) {
+            return Err("Endpoint does not accept an empty tag".to_string());
+        }
+        if let Some(tag) = tags
+            .iter()
+            .find(|tag| tag.contains(|c: char| c.is_whitespace() || c == '"'))
+        {
+            return Err(format!(
+                "Endpoint does not accept whitespace or `\"` in tag `{}`",
+                tag
+            ));
+        }
+
+        let has_terms = matches!(self.terms, Some(Some(ref xs)) if !xs.is_empty());
+        let has_tags = matches!(self.tags, Some(Some(ref xs)) if !xs.is_empty());
+        let has_filter = [
+            self.after.flatten().is_some(),
+            self.before.flatten().is_some(),
+            self.visibility.flatten().is_some(),
+            self.unread.flatten().unwrap_or(false),
+        ]
+        .contains(&true);
+        if !(has_terms || has_tags || has_filter) {
+            return Err("Endpoint requires at least one search criterion".to_string());
+        }
+
+        if let (Some(Some(after)), Some(Some(before))) = (self.after, self.before) {
+            if after > before {
+                return Err(format!(
+                    "Endpoint requires `after` ({}) to not be later than `before` ({})",
+                    after, before
+                ));
+            }
+        }
+
+        if let Some(Some(count)) = self.count {
+            if count > 100 {
+                return Err("Endpoint only accepts `count` of 100 or less".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> Search<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> SearchBuilder<'a> {
+        SearchBuilder::default()
+    }
+
+    /// The search query sent as the `q` parameter
+    pub fn search_query(&self) -> String {
+        let mut parts = Vec::new();
+
+        for term in self.terms.iter().flat_map(|xs| xs.iter()) {
+            // Quoting keeps a term like `is:private` from becoming an operator.
+            if term.contains(|c: char| c.is_whitespace() || c == ':') {
+                parts.push(format!("\"{}\"", term));
+            } else {
+                parts.push(term.to_string());
+            }
+        }
+        for tag in self.tags.iter().flat_map(|xs| xs.iter()) {
+            parts.push(format!("tag:{}", tag));
+        }
+        if let Some(after) = self.after {
+            parts.push(format!("after:{}", after.format("%Y-%m-%d")));
+        }
+        if let Some(before) = self.before {
+            parts.push(format!("before:{}", before.format("%Y-%m-%d")));
+        }
+        match self.visibility {
+            Some(Visibility::Public) => parts.push("is:public".to_string()),
+            Some(Visibility::Private) => parts.push("is:private".to_string()),
+            None => (),
+        }
+        if self.unread == Some(true) {
+            parts.push("is:unread".to_string());
+        }
+
+        parts.join(" ")
+    }
+}
+
+impl<'a> Endpoint for Search<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "search".into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+
+        params
+            .push("q", self.search_query())
+            .push_opt("start", self.start)
+            .push_opt("count", self.count);
+
+        params
+    }
+}
+
+impl<'a> Pageable for Search<'a> {
+    fn results_pointer(&self) -> &'static str {
+        "/bookmarks"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use serde_json::json;
+
+    use crate::api::v2::search::{Search, Visibility};
+    use crate::api::{self, Pagination, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::Bookmark;
+
+    #[test]
+    fn criterion_is_required() {
+        let err = Search::builder().count(10).build().unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint requires at least one search criterion"
+        )
+    }
+
+    #[test]
+    fn date_range_is_ordered() {
+        let err = Search::builder()
+            .after(NaiveDate::from_ymd_opt(2021, 5, 6).expect("Valid date"))
+            .before(NaiveDate::from_ymd_opt(2021, 5, 5).expect("Valid date"))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint requires `after` (2021-05-06) to not be later than `before` (2021-05-05)"
+        )
+    }
+
+    #[test]
+    fn query_terms() {
+        let endpoint = Search::builder()
+            .terms(vec!["rust", "error handling"])
+            .build()
+            .unwrap();
+        assert_eq!(endpoint.search_query(), r#"rust "error handling""#);
+    }
+
+    #[test]
+    fn query_terms_with_colons() {
+        let endpoint = Search::builder()
+            .terms(vec!["tag:rust", "is:private"])
+            .build()
+            .unwrap();
+        assert_eq!(endpoint.search_query(), r#""tag:rust" "is:private""#);
+    }
+
+    #[test]
+    fn blank_terms_are_rejected() {
+        for term in ["", "  "] {
+            let err = Search::builder()
+                .terms(vec!["rust", term])
+                .build()
+                .unwrap_err();
+            assert_eq!(
+                &err.to_string(),
+                "Endpoint does not accept a blank search term"
+            )
+        }
+    }
+
+    #[test]
+    fn empty_tags_are_rejected() {
+        let err = Search::builder().tags(vec![""]).build().unwrap_err();
+        assert_eq!(&err.to_string(), "Endpoint does not accept an empty tag")
+    }
+
+    #[test]
+    fn term_quotes_are_rejected() {
+        let err = Search::builder()
+            .terms(vec!["rust", "\"error\" handling"])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            r#"Endpoint does not accept `"` in search term `"error" handling`"#
+        )
+    }
+
+    #[test]
+    fn tag_whitespace_is_rejected() {
+        let err = Search::builder().tags(vec!["to read"]).build().unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint does not accept whitespace or `\"` in tag `to read`"
+        )
+    }
+
+    #[test]
+    fn tag_quotes_are_rejected() {
+        let err = Search::builder()
+            .tags(vec!["say\"what"])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            r#"Endpoint does not accept whitespace or `"` in tag `say"what`"#
+        )
+    }
+
+    #[test]
+    fn query_filters() {
+        let endpoint = Search::builder()
+            .tags(vec!["one", "two"])
+            .after(NaiveDate::from_ymd_opt(2001, 5, 6).expect("Valid date"))
+            .before(NaiveDate::from_ymd_opt(2010, 8, 9).expect("Valid date"))
+            .visibility(Visibility::Private)
+            .unread(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            endpoint.search_query(),
+            "tag:one tag:two after:2001-05-06 before:2010-08-09 is:private is:unread"
+        );
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/search")
+            .add_query_params(&[("q", "rust tag:lang is:public")])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Search::builder()
+            .terms(vec!["rust"])
+            .tags(vec!["lang"])
+            .visibility(Visibility::Public)
+            .build()
+            .unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn endpoint_pagination() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/search")
+            .add_query_params(&[("q", "rust"), ("start", "40"), ("count", "20")])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Search::builder()
+            .terms(vec!["rust"])
+            .start(40)
+            .count(20)
+            .build()
+            .unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn endpoint_count_101() {
+        let err = Search::builder()
+            .terms(vec!["rust"])
+            .count(101)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint only accepts `count` of 100 or less"
+        )
+    }
+
+    #[test]
+    fn paged() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/search")
+            .add_query_params(&[("q", "rust"), ("start", "40"), ("count", "1")])
+            .build()
+            .unwrap();
+        let bookmark = json!({
+            "id": "abc123",
+            "url": "https://some.web.site",
+            "title": "Words",
+            "description": "",
+            "tags": ["rust"],
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "private": false,
+            "unread": false,
+        });
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!({
+                "query": "rust",
+                "total": 3,
+                "start": 40,
+                "bookmarks": [bookmark],
+            }),
+        );
+
+        let endpoint = Search::builder()
+            .terms(vec!["rust"])
+            .start(40)
+            .build()
+            .unwrap();
+        let bookmarks: Vec<Bookmark> = api::paged(endpoint, Pagination::Limit(1))
+            .query(&client)
+            .unwrap();
+        assert_eq!(bookmarks[0].id, "abc123");
+    }
+}
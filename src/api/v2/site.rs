@@ -5,3 +5,15 @@
 // except according to those terms.
 
 //! Builders to interact with the set of site endpoints.
+//!
+//! These are the site-wide feeds of public bookmarks from all users.
+mod popular;
+mod recent;
+mod tagged;
+
+#[doc(inline)]
+pub use self::popular::Popular;
+#[doc(inline)]
+pub use self::recent::Recent;
+#[doc(inline)]
+pub use self::tagged::Tagged;
@@ -0,0 +1,113 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create a Popular endpoint for the site feeds.
+///
+/// Returns the most saved public bookmarks of the moment.
+///
+/// # Arguments
+/// * `count` - number of results to return (up to 100)
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::site::Popular;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let popular_endpoint = Popular::builder().build().unwrap();
+/// assert_eq!(popular_endpoint.endpoint(), "site/popular");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Builder)]
+#[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
+pub struct Popular {
+    /// Number of results to return
+    #[builder(default)]
+    count: Option<u8>,
+}
+
+impl PopularBuilder {
+    // Count is limited to 100.
+    fn validate(&self) -> Result<(), String> {
+        if let Some(Some(count)) = self.count {
+            if count > 100 {
+                return Err("Endpoint only accepts `count` of 100 or less".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Popular {
+    /// Create a builder for the endpoint
+    pub fn builder() -> PopularBuilder {
+        PopularBuilder::default()
+    }
+}
+
+impl Endpoint for Popular {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "site/popular".into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+
+        params.push_opt("count", self.count);
+
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::api::v2::site::Popular;
+    use crate::api::Query;
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::PopularBookmarks;
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/site/popular")
+            .add_query_params(&[("count", "10")])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!([{"url": "https://example.com", "title": "Example", "tags": ["example"], "saves": 12}]),
+        );
+
+        let feed: PopularBookmarks = Popular::builder()
+            .count(10)
+            .build()
+            .unwrap()
+            .query(&client)
+            .unwrap();
+        assert_eq!(feed[0].saves, 12);
+    }
+
+    #[test]
+    fn endpoint_count_101() {
+        let err = Popular::builder().count(101).build().unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint only accepts `count` of 100 or less"
+        )
+    }
+}
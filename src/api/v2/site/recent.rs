@@ -0,0 +1,113 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create a Recent endpoint for the site feeds.
+///
+/// Returns the latest public bookmarks from all users.
+///
+/// # Arguments
+/// * `count` - number of results to return (up to 100)
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::site::Recent;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let recent_endpoint = Recent::builder().build().unwrap();
+/// assert_eq!(recent_endpoint.endpoint(), "site/recent");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Builder)]
+#[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
+pub struct Recent {
+    /// Number of results to return
+    #[builder(default)]
+    count: Option<u8>,
+}
+
+impl RecentBuilder {
+    // Count is limited to 100.
+    fn validate(&self) -> Result<(), String> {
+        if let Some(Some(count)) = self.count {
+            if count > 100 {
+                return Err("Endpoint only accepts `count` of 100 or less".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Recent {
+    /// Create a builder for the endpoint
+    pub fn builder() -> RecentBuilder {
+        RecentBuilder::default()
+    }
+}
+
+impl Endpoint for Recent {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "site/recent".into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+
+        params.push_opt("count", self.count);
+
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::api::v2::site::Recent;
+    use crate::api::Query;
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::PublicBookmarks;
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/site/recent")
+            .add_query_params(&[("count", "10")])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!([{"user": "someone", "url": "https://example.com", "title": "Example", "description": "", "tags": [], "created_at": "2024-01-01T00:00:00Z"}]),
+        );
+
+        let feed: PublicBookmarks = Recent::builder()
+            .count(10)
+            .build()
+            .unwrap()
+            .query(&client)
+            .unwrap();
+        assert_eq!(feed[0].user, "someone");
+    }
+
+    #[test]
+    fn endpoint_count_101() {
+        let err = Recent::builder().count(101).build().unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint only accepts `count` of 100 or less"
+        )
+    }
+}
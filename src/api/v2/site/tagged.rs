@@ -0,0 +1,128 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use crate::api::v2::{check_path_segment, path_segment};
+use derive_builder::Builder;
+
+/// Create a Tagged endpoint for the site feeds.
+///
+/// Returns the latest public bookmarks from all users with a tag.
+///
+/// # Arguments
+/// This builder requires the tag to follow.
+/// * `tag` - the tag name
+///
+/// The remaining argument is optional.
+/// * `count` - number of results to return (up to 100)
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::site::Tagged;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let tagged_endpoint = Tagged::builder().tag("rust").build().unwrap();
+/// assert_eq!(tagged_endpoint.endpoint(), "site/tag/rust");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
+pub struct Tagged<'a> {
+    /// The tag name
+    #[builder(setter(into))]
+    tag: Cow<'a, str>,
+    /// Number of results to return
+    #[builder(default)]
+    count: Option<u8>,
+}
+
+impl<'a> TaggedBuilder<'a> {
+    // The tag must be usable as a path segment and count is limited to 100.
+    fn validate(&self) -> Result<(), String> {
+        if let Some(ref tag) = self.tag {
+            check_path_segment("tag", tag)?;
+        }
+        if let Some(Some(count)) = self.count {
+            if count > 100 {
+                return Err("Endpoint only accepts `count` of 100 or less".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Tagged<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> TaggedBuilder<'a> {
+        TaggedBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Tagged<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("site/tag/{}", path_segment(&self.tag)).into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+
+        params.push_opt("count", self.count);
+
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::v2::site::Tagged;
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+
+    #[test]
+    fn tag_is_required() {
+        let err = Tagged::builder().count(10).build().unwrap_err();
+        assert_eq!(&err.to_string(), "`tag` must be initialized")
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/site/tag/c%2B%2B")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Tagged::builder().tag("c++").build().unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn endpoint_count_101() {
+        let err = Tagged::builder()
+            .tag("rust")
+            .count(101)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint only accepts `count` of 100 or less"
+        )
+    }
+
+    #[test]
+    fn dot_segments_are_rejected() {
+        let err = Tagged::builder().tag("..").build().unwrap_err();
+        assert_eq!(&err.to_string(), "Endpoint does not accept `..` as `tag`");
+    }
+}
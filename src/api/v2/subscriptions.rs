@@ -5,3 +5,15 @@
 // except according to those terms.
 
 //! Builders to interact with the set of subscriptions endpoints.
+//!
+//! A subscription follows a user, a tag, or a tag used by one user.
+mod add;
+mod list;
+mod remove;
+
+#[doc(inline)]
+pub use self::add::Add;
+#[doc(inline)]
+pub use self::list::List;
+#[doc(inline)]
+pub use self::remove::Remove;
@@ -0,0 +1,121 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create an Add endpoint for subscriptions.
+///
+/// # Arguments
+/// This builder takes two optional arguments, at least one of which
+/// must be given.
+/// * `user` - the user to follow
+/// * `tag` - the tag to follow (only from `user`, if given)
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::subscriptions::Add;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let add_endpoint = Add::builder().user("someone").tag("rust").build().unwrap();
+/// assert_eq!(add_endpoint.endpoint(), "subscriptions");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
+pub struct Add<'a> {
+    /// The user to follow
+    #[builder(setter(into), default)]
+    user: Option<Cow<'a, str>>,
+    /// The tag to follow
+    #[builder(setter(into), default)]
+    tag: Option<Cow<'a, str>>,
+}
+
+impl<'a> AddBuilder<'a> {
+    // Ensure there is a user or a tag to follow
+    fn validate(&self) -> Result<(), String> {
+        match (&self.user, &self.tag) {
+            (Some(Some(_)), _) | (_, Some(Some(_))) => Ok(()),
+            _ => Err("Endpoint requires a `user` or a `tag`".to_string()),
+        }
+    }
+}
+
+impl<'a> Add<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> AddBuilder<'a> {
+        AddBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Add<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "subscriptions".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+
+        params
+            .push_opt("user", self.user.as_ref())
+            .push_opt("tag", self.tag.as_ref());
+        params.into_body()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+
+    use crate::api::v2::subscriptions::Add;
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+
+    #[test]
+    fn user_or_tag_is_required() {
+        let err = Add::builder().build().unwrap_err();
+        assert_eq!(&err.to_string(), "Endpoint requires a `user` or a `tag`")
+    }
+
+    #[test]
+    fn endpoint_user() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/subscriptions")
+            .content_type("application/x-www-form-urlencoded")
+            .body_str("user=someone")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Add::builder().user("someone").build().unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn endpoint_user_tag() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/subscriptions")
+            .content_type("application/x-www-form-urlencoded")
+            .body_str("user=someone&tag=rust")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Add::builder().user("someone").tag("rust").build().unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+}
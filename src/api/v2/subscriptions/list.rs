@@ -0,0 +1,75 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create a List endpoint for subscriptions.
+///
+/// There are no arguments for this endpoint.
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::subscriptions::List;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let list_endpoint = List::builder().build().unwrap();
+/// assert_eq!(list_endpoint.endpoint(), "subscriptions");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Builder)]
+pub struct List {}
+
+impl List {
+    /// Create a builder for the endpoint
+    pub fn builder() -> ListBuilder {
+        ListBuilder::default()
+    }
+}
+
+impl Endpoint for List {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "subscriptions".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::api::v2::subscriptions::List;
+    use crate::api::Query;
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::Subscriptions;
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/subscriptions")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!([
+                {"user": "someone", "tag": null, "created_at": "2024-01-01T00:00:00Z"},
+                {"user": null, "tag": "rust", "created_at": "2024-01-02T00:00:00Z"},
+            ]),
+        );
+
+        let subs: Subscriptions = List::builder().build().unwrap().query(&client).unwrap();
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs[0].user.as_deref(), Some("someone"));
+        assert_eq!(subs[1].tag.as_deref(), Some("rust"));
+    }
+}
@@ -0,0 +1,106 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create a Remove endpoint for subscriptions.
+///
+/// # Arguments
+/// This builder takes two optional arguments, at least one of which
+/// must be given. They must match the subscription as it was added.
+/// * `user` - the followed user
+/// * `tag` - the followed tag
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::subscriptions::Remove;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let remove_endpoint = Remove::builder().tag("rust").build().unwrap();
+/// assert_eq!(remove_endpoint.endpoint(), "subscriptions");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
+pub struct Remove<'a> {
+    /// The followed user
+    #[builder(setter(into), default)]
+    user: Option<Cow<'a, str>>,
+    /// The followed tag
+    #[builder(setter(into), default)]
+    tag: Option<Cow<'a, str>>,
+}
+
+impl<'a> RemoveBuilder<'a> {
+    // Ensure there is a user or a tag to stop following
+    fn validate(&self) -> Result<(), String> {
+        match (&self.user, &self.tag) {
+            (Some(Some(_)), _) | (_, Some(Some(_))) => Ok(()),
+            _ => Err("Endpoint requires a `user` or a `tag`".to_string()),
+        }
+    }
+}
+
+impl<'a> Remove<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> RemoveBuilder<'a> {
+        RemoveBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Remove<'a> {
+    fn method(&self) -> Method {
+        Method::DELETE
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "subscriptions".into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+
+        params
+            .push_opt("user", self.user.as_ref())
+            .push_opt("tag", self.tag.as_ref());
+
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+
+    use crate::api::v2::subscriptions::Remove;
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+
+    #[test]
+    fn user_or_tag_is_required() {
+        let err = Remove::builder().build().unwrap_err();
+        assert_eq!(&err.to_string(), "Endpoint requires a `user` or a `tag`")
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::DELETE)
+            .endpoint("v2/subscriptions")
+            .add_query_params(&[("user", "someone")])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Remove::builder().user("someone").build().unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+}
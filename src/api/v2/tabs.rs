@@ -5,3 +5,15 @@
 // except according to those terms.
 
 //! Builders to interact with the set of tabs endpoints.
+//!
+//! These endpoints save and restore sets of browser windows and their tabs.
+mod get;
+mod list;
+mod save;
+
+#[doc(inline)]
+pub use self::get::Get;
+#[doc(inline)]
+pub use self::list::List;
+#[doc(inline)]
+pub use self::save::Save;
@@ -0,0 +1,113 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use crate::api::v2::{check_path_segment, path_segment};
+use derive_builder::Builder;
+
+/// Create a Get endpoint for a saved tab set.
+///
+/// # Arguments
+/// * `id` - id of the tab set to retrieve
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::tabs::Get;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let get_endpoint = Get::builder().id("t-1").build().unwrap();
+/// assert_eq!(get_endpoint.endpoint(), "tabs/t-1");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct Get<'a> {
+    /// The tab set id
+    #[builder(setter(into))]
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetBuilder<'a> {
+    // The id must be usable as a path segment
+    fn validate(&self) -> Result<(), String> {
+        match self.id {
+            Some(ref id) => check_path_segment("id", id),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Get<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> GetBuilder<'a> {
+        GetBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Get<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("tabs/{}", path_segment(&self.id)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::api::v2::tabs::Get;
+    use crate::api::Query;
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::TabSet;
+
+    #[test]
+    fn id_is_required() {
+        let err = Get::builder().build().unwrap_err();
+        assert_eq!(&err.to_string(), "`id` must be initialized")
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/tabs/t-1")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!({
+                "windows": [
+                    [{"title": "One", "url": "https://one.example"}],
+                    [
+                        {"title": "Two", "url": "https://two.example"},
+                        {"title": "Three", "url": "https://three.example"},
+                    ],
+                ],
+            }),
+        );
+
+        let set: TabSet = Get::builder()
+            .id("t-1")
+            .build()
+            .unwrap()
+            .query(&client)
+            .unwrap();
+        assert_eq!(set.windows.len(), 2);
+        assert_eq!(set.windows[1][1].title, "Three");
+    }
+
+    #[test]
+    fn dot_segments_are_rejected() {
+        let err = Get::builder().id(".").build().unwrap_err();
+        assert_eq!(&err.to_string(), "Endpoint does not accept `.` as `id`");
+    }
+}
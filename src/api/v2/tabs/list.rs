@@ -0,0 +1,74 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create a List endpoint for saved tab sets.
+///
+/// There are no arguments for this endpoint.
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::tabs::List;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let list_endpoint = List::builder().build().unwrap();
+/// assert_eq!(list_endpoint.endpoint(), "tabs");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Builder)]
+pub struct List {}
+
+impl List {
+    /// Create a builder for the endpoint
+    pub fn builder() -> ListBuilder {
+        ListBuilder::default()
+    }
+}
+
+impl Endpoint for List {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "tabs".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::api::v2::tabs::List;
+    use crate::api::Query;
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::TabSetSummaries;
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder().endpoint("v2/tabs").build().unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!([{
+                "id": "t-1",
+                "title": "research",
+                "created_at": "2024-01-01T00:00:00Z",
+                "windows": 2,
+                "tabs": 9,
+            }]),
+        );
+
+        let sets: TabSetSummaries = List::builder().build().unwrap().query(&client).unwrap();
+        assert_eq!(sets[0].id, "t-1");
+        assert_eq!(sets[0].tabs, 9);
+    }
+}
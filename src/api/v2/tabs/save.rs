@@ -0,0 +1,167 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use derive_builder::Builder;
+use serde::Serialize;
+
+use crate::api::endpoint_prelude::*;
+use crate::types::v2::TabSet;
+
+/// Create a Save endpoint for a set of browser tabs.
+///
+/// The windows and their tabs are sent as a JSON body, in the same
+/// [`TabSet`] form the `Get` endpoint returns them.
+///
+/// # Arguments
+/// This builder requires the tabs to save.
+/// * `tabs` - the tabs of each window (at least one tab in total)
+///
+/// The remaining argument is optional.
+/// * `title` - a title for the tab set
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::tabs::Save;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// # use crate::pinboard_rs::types::v2::{Tab, TabSet};
+/// let tab = Tab {
+///     title: "Example".to_string(),
+///     url: "https://example.com".to_string(),
+/// };
+/// let tabs = TabSet {
+///     windows: vec![vec![tab]],
+/// };
+/// let save_endpoint = Save::builder().tabs(tabs).build().unwrap();
+/// assert_eq!(save_endpoint.endpoint(), "tabs");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder, Serialize)]
+#[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
+pub struct Save<'a> {
+    /// A title for the tab set
+    #[builder(setter(into), default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<Cow<'a, str>>,
+    /// The tabs of each window
+    #[serde(flatten)]
+    tabs: TabSet,
+}
+
+impl<'a> SaveBuilder<'a> {
+    // Ensure there is at least one tab to save
+    fn validate(&self) -> Result<(), String> {
+        if let Some(ref tabs) = self.tabs {
+            if tabs.windows.iter().all(Vec::is_empty) {
+                return Err("Endpoint requires at least 1 tab to save".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Save<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> SaveBuilder<'a> {
+        SaveBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Save<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "tabs".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        Ok(Some(("application/json", serde_json::to_vec(self)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+
+    use crate::api::v2::tabs::Save;
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::{Tab, TabSet};
+
+    fn tab(title: &str) -> Tab {
+        Tab {
+            title: title.to_string(),
+            url: format!("https://{}.example", title),
+        }
+    }
+
+    fn tabs(windows: Vec<Vec<Tab>>) -> TabSet {
+        TabSet { windows }
+    }
+
+    #[test]
+    fn tabs_is_required() {
+        let err = Save::builder().title("research").build().unwrap_err();
+        assert_eq!(&err.to_string(), "`tabs` must be initialized")
+    }
+
+    #[test]
+    fn tab_is_required() {
+        let err = Save::builder()
+            .tabs(tabs(vec![vec![], vec![]]))
+            .build()
+            .unwrap_err();
+        assert_eq!(&err.to_string(), "Endpoint requires at least 1 tab to save")
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/tabs")
+            .content_type("application/json")
+            .body_str(concat!(
+                r#"{"windows":[[{"title":"one","url":"https://one.example"}],"#,
+                r#"[{"title":"two","url":"https://two.example"}]]}"#,
+            ))
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Save::builder()
+            .tabs(tabs(vec![vec![tab("one")], vec![tab("two")]]))
+            .build()
+            .unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn endpoint_title() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/tabs")
+            .content_type("application/json")
+            .body_str(
+                r#"{"title":"research","windows":[[{"title":"one","url":"https://one.example"}]]}"#,
+            )
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Save::builder()
+            .title("research")
+            .tabs(tabs(vec![vec![tab("one")]]))
+            .build()
+            .unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+}
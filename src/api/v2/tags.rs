@@ -5,3 +5,22 @@
 // except according to those terms.
 
 //! Builders to interact with the set of tags endpoints.
+//!
+//! Unlike the V1 endpoints, the merge and delete builders operate on
+//! several tags in a single call. Rename still takes a single old/new pair.
+mod delete;
+mod get;
+mod list;
+mod merge;
+mod rename;
+
+#[doc(inline)]
+pub use self::delete::Delete;
+#[doc(inline)]
+pub use self::get::Get;
+#[doc(inline)]
+pub use self::list::{List, TagOrder};
+#[doc(inline)]
+pub use self::merge::Merge;
+#[doc(inline)]
+pub use self::rename::Rename;
@@ -0,0 +1,140 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create a Delete endpoint for tags.
+///
+/// The tags are removed from every bookmark; the bookmarks themselves
+/// are left in place.
+///
+/// # Arguments
+/// * `tags` - the tags to delete (at least one)
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::tags::Delete;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let del_endpoint = Delete::builder().tags(vec!["go", "java"]).build().unwrap();
+/// assert_eq!(del_endpoint.endpoint(), "tags/delete");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct Delete<'a> {
+    /// The tags to delete
+    #[builder(setter(into))]
+    tags: Cow<'a, [&'a str]>,
+}
+
+impl<'a> DeleteBuilder<'a> {
+    // Ensure there is at least one tag to delete
+    fn validate(&self) -> Result<(), String> {
+        if let Some(ref xs) = self.tags {
+            if xs.is_empty() {
+                return Err("Endpoint requires at least 1 tag to delete".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Delete<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> DeleteBuilder<'a> {
+        DeleteBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Delete<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "tags/delete".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+
+        params.push("tags", self.tags.join(" "));
+        params.into_body()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+    use serde_json::json;
+
+    use crate::api::v2::tags::Delete;
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::TagsChanged;
+
+    #[test]
+    fn tags_is_required() {
+        let err = Delete::builder().build().unwrap_err();
+        assert_eq!(&err.to_string(), "`tags` must be initialized")
+    }
+
+    #[test]
+    fn tags_is_not_empty() {
+        let err = Delete::builder().tags(vec![]).build().unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint requires at least 1 tag to delete"
+        )
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/tags/delete")
+            .content_type("application/x-www-form-urlencoded")
+            .body_str("tags=buh-bye+see-ya")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Delete::builder()
+            .tags(vec!["buh-bye", "see-ya"])
+            .build()
+            .unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn tags_changed() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/tags/delete")
+            .content_type("application/x-www-form-urlencoded")
+            .body_str("tags=buh-bye+see-ya")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!({"tags": ["buh-bye", "see-ya"], "bookmarks": 7}),
+        );
+
+        let endpoint = Delete::builder()
+            .tags(vec!["buh-bye", "see-ya"])
+            .build()
+            .unwrap();
+        let changed: TagsChanged = endpoint.query(&client).unwrap();
+        assert_eq!(changed.tags, vec!["buh-bye", "see-ya"]);
+        assert_eq!(changed.bookmarks, 7);
+    }
+}
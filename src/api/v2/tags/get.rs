@@ -0,0 +1,135 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use crate::api::v2::{check_path_segment, path_segment};
+use derive_builder::Builder;
+
+/// Create a Get endpoint for the metadata of a single tag.
+///
+/// Returns the usage counts and first/last use of the tag.
+///
+/// # Arguments
+/// * `tag` - the tag name
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::tags::Get;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let get_endpoint = Get::builder().tag("rust").build().unwrap();
+/// assert_eq!(get_endpoint.endpoint(), "tags/rust");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct Get<'a> {
+    /// The tag name
+    #[builder(setter(into))]
+    tag: Cow<'a, str>,
+}
+
+impl<'a> GetBuilder<'a> {
+    // The tag must be usable as a path segment
+    fn validate(&self) -> Result<(), String> {
+        match self.tag {
+            Some(ref tag) => check_path_segment("tag", tag),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Get<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> GetBuilder<'a> {
+        GetBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Get<'a> {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        format!("tags/{}", path_segment(&self.tag)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::api::v2::tags::Get;
+    use crate::api::{self, Endpoint, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::TagInfo;
+
+    #[test]
+    fn tag_is_required() {
+        let err = Get::builder().build().unwrap_err();
+        assert_eq!(&err.to_string(), "`tag` must be initialized")
+    }
+
+    #[test]
+    fn tag_is_escaped() {
+        let endpoint = Get::builder().tag("c#/.net").build().unwrap();
+        assert_eq!(endpoint.endpoint(), "tags/c%23%2F.net");
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/tags/rust")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Get::builder().tag("rust").build().unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn space_is_percent_encoded() {
+        let endpoint = Get::builder().tag("to read").build().unwrap();
+        assert_eq!(endpoint.endpoint(), "tags/to%20read");
+    }
+
+    #[test]
+    fn tag_info() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/tags/rust")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!({
+                "name": "rust",
+                "count": 12,
+                "public_count": 10,
+                "private_count": 2,
+                "first_used": "2021-01-01T00:00:00Z",
+                "last_used": null,
+            }),
+        );
+
+        let endpoint = Get::builder().tag("rust").build().unwrap();
+        let info: TagInfo = endpoint.query(&client).unwrap();
+        assert_eq!(info.name, "rust");
+        assert_eq!(info.count, 12);
+        assert_eq!(info.last_used, None);
+    }
+
+    #[test]
+    fn dot_segments_are_rejected() {
+        let err = Get::builder().tag("..").build().unwrap_err();
+        assert_eq!(&err.to_string(), "Endpoint does not accept `..` as `tag`");
+    }
+}
@@ -0,0 +1,155 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use crate::api::ParamValue;
+use derive_builder::Builder;
+
+/// The order in which tags are listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagOrder {
+    /// Alphabetical by tag name
+    Alpha,
+    /// Most used tags first
+    Count,
+}
+
+impl ParamValue<'static> for TagOrder {
+    fn as_value(&self) -> Cow<'static, str> {
+        match self {
+            TagOrder::Alpha => "alpha".into(),
+            TagOrder::Count => "count".into(),
+        }
+    }
+}
+
+/// Create a List endpoint for tags.
+///
+/// Returns every tag along with the number of bookmarks using it.
+///
+/// # Arguments
+/// This builder takes two optional arguments.
+/// * `order` - sort the tags alphabetically or by count
+/// * `min_count` - only return tags used at least this many times
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::tags::List;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let list_endpoint = List::builder().min_count(2).build().unwrap();
+/// assert_eq!(list_endpoint.endpoint(), "tags");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Builder)]
+#[builder(setter(strip_option))]
+pub struct List {
+    /// Ordering of the returned tags
+    #[builder(default)]
+    order: Option<TagOrder>,
+    /// Minimum number of bookmarks using the tag
+    #[builder(default)]
+    min_count: Option<u64>,
+}
+
+impl List {
+    /// Create a builder for the endpoint
+    pub fn builder() -> ListBuilder {
+        ListBuilder::default()
+    }
+}
+
+impl Endpoint for List {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "tags".into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+
+        params
+            .push_opt("order", self.order)
+            .push_opt("min_count", self.min_count);
+
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::api::v2::tags::{List, TagOrder};
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::{Tag, TagList};
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder().endpoint("v2/tags").build().unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = List::builder().build().unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn endpoint_order() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/tags")
+            .add_query_params(&[("order", "count")])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = List::builder().order(TagOrder::Count).build().unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn endpoint_min_count() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/tags")
+            .add_query_params(&[("min_count", "5")])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = List::builder().min_count(5).build().unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn tag_list() {
+        let endpoint = ExpectedUrl::builder().endpoint("v2/tags").build().unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!({
+                "count": 2,
+                "tags": [{"name": "rust", "count": 12}, {"name": "python", "count": 3}],
+            }),
+        );
+
+        let endpoint = List::builder().build().unwrap();
+        let list: TagList = endpoint.query(&client).unwrap();
+        assert_eq!(list.count, 2);
+        assert_eq!(
+            list.tags[1],
+            Tag {
+                name: "python".to_string(),
+                count: 3
+            }
+        );
+    }
+}
@@ -0,0 +1,162 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create a Merge endpoint for tags.
+///
+/// Every bookmark carrying one of the `tags` is retagged with `target`,
+/// and the merged tags are removed.
+///
+/// # Arguments
+/// There are two arguments for this builder.
+/// * `tags` - the tags to merge (at least one)
+/// * `target` - the tag to merge them into
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::tags::Merge;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let merge_endpoint = Merge::builder()
+///                      .tags(vec!["rustlang", "rust-lang"])
+///                      .target("rust")
+///                      .build().unwrap();
+/// assert_eq!(merge_endpoint.endpoint(), "tags/merge");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct Merge<'a> {
+    /// The tags to merge
+    #[builder(setter(into))]
+    tags: Cow<'a, [&'a str]>,
+    /// The tag to merge into
+    #[builder(setter(into))]
+    target: Cow<'a, str>,
+}
+
+impl<'a> MergeBuilder<'a> {
+    // Ensure there is at least one tag to merge
+    fn validate(&self) -> Result<(), String> {
+        if let Some(ref xs) = self.tags {
+            if xs.is_empty() {
+                return Err("Endpoint requires at least 1 tag to merge".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Merge<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> MergeBuilder<'a> {
+        MergeBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Merge<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "tags/merge".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+
+        params
+            .push("tags", self.tags.join(" "))
+            .push("target", self.target.as_ref());
+        params.into_body()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+    use serde_json::json;
+
+    use crate::api::v2::tags::Merge;
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::TagsChanged;
+
+    #[test]
+    fn tags_is_required() {
+        let err = Merge::builder().target("rust").build().unwrap_err();
+        assert_eq!(&err.to_string(), "`tags` must be initialized")
+    }
+
+    #[test]
+    fn tags_is_not_empty() {
+        let err = Merge::builder()
+            .tags(vec![])
+            .target("rust")
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint requires at least 1 tag to merge"
+        )
+    }
+
+    #[test]
+    fn target_is_required() {
+        let err = Merge::builder().tags(vec!["rs"]).build().unwrap_err();
+        assert_eq!(&err.to_string(), "`target` must be initialized")
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/tags/merge")
+            .content_type("application/x-www-form-urlencoded")
+            .body_str("tags=rs+rustlang&target=rust")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Merge::builder()
+            .tags(vec!["rs", "rustlang"])
+            .target("rust")
+            .build()
+            .unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn tags_changed() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/tags/merge")
+            .content_type("application/x-www-form-urlencoded")
+            .body_str("tags=rs+rustlang&target=rust")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!({"tags": ["rs", "rustlang"], "bookmarks": 7}),
+        );
+
+        let endpoint = Merge::builder()
+            .tags(vec!["rs", "rustlang"])
+            .target("rust")
+            .build()
+            .unwrap();
+        let changed: TagsChanged = endpoint.query(&client).unwrap();
+        assert_eq!(changed.tags, vec!["rs", "rustlang"]);
+        assert_eq!(changed.bookmarks, 7);
+    }
+}
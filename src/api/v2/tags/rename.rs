@@ -0,0 +1,128 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create a Rename endpoint for tags.
+///
+/// # Arguments
+/// There are two arguments for this builder.
+/// * `old` - name of the old (original) tag
+/// * `new` - name of the new tag name to use
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::tags::Rename;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let rename_endpoint = Rename::builder().old("C").new("C++").build().unwrap();
+/// assert_eq!(rename_endpoint.endpoint(), "tags/rename");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+pub struct Rename<'a> {
+    /// The old tag name
+    #[builder(setter(into))]
+    old: Cow<'a, str>,
+    /// The new tag name
+    #[builder(setter(into))]
+    new: Cow<'a, str>,
+}
+
+impl<'a> Rename<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> RenameBuilder<'a> {
+        RenameBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Rename<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "tags/rename".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+
+        params
+            .push("old", self.old.as_ref())
+            .push("new", self.new.as_ref());
+        params.into_body()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+    use serde_json::json;
+
+    use crate::api::v2::tags::Rename;
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::TagsChanged;
+
+    #[test]
+    fn old_is_required() {
+        let err = Rename::builder().build().unwrap_err();
+        assert_eq!(&err.to_string(), "`old` must be initialized")
+    }
+
+    #[test]
+    fn new_is_required() {
+        let err = Rename::builder().old("old").build().unwrap_err();
+        assert_eq!(&err.to_string(), "`new` must be initialized")
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/tags/rename")
+            .content_type("application/x-www-form-urlencoded")
+            .body_str("old=buh-bye&new=see-ya")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Rename::builder()
+            .old("buh-bye")
+            .new("see-ya")
+            .build()
+            .unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn tags_changed() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/tags/rename")
+            .content_type("application/x-www-form-urlencoded")
+            .body_str("old=buh-bye&new=see-ya")
+            .build()
+            .unwrap();
+        let client =
+            SingleTestClient::new_json(endpoint, &json!({"tags": ["buh-bye"], "bookmarks": 7}));
+
+        let endpoint = Rename::builder()
+            .old("buh-bye")
+            .new("see-ya")
+            .build()
+            .unwrap();
+        let changed: TagsChanged = endpoint.query(&client).unwrap();
+        assert_eq!(changed.tags, vec!["buh-bye"]);
+        assert_eq!(changed.bookmarks, 7);
+    }
+}
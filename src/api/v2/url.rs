@@ -5,3 +5,81 @@
 // except according to those terms.
 
 //! Builders to interact with the set of url endpoints.
+//!
+//! These endpoints look up what Pinboard knows about a single URL.
+//! Use [`normalize`] (or the `normalized_url` builder setters) to drop
+//! the fragment and tracking parameters from a URL before looking it up.
+mod info;
+mod savers;
+
+#[doc(inline)]
+pub use self::info::Info;
+#[doc(inline)]
+pub use self::savers::Savers;
+
+/// Remove the fragment and `utm_*` tracking parameters from a URL.
+///
+/// The remaining query parameters are kept exactly as written, and an
+/// emptied query string is dropped. Scheme and host case and default
+/// ports are already normalized by `url::Url`.
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use pinboard_rs::api::v2::url::normalize;
+/// # use url::Url;
+/// let url = Url::parse("HTTPS://Example.com:443/a?utm_source=x&id=1#top").unwrap();
+/// assert_eq!(normalize(&url).as_str(), "https://example.com/a?id=1");
+/// # }
+/// ```
+pub fn normalize(url: &::url::Url) -> ::url::Url {
+    let mut normalized = url.clone();
+    normalized.set_fragment(None);
+
+    if let Some(query) = url.query() {
+        // Filter the raw pairs so that the kept ones are not re-encoded
+        let kept: Vec<&str> = query
+            .split('&')
+            .filter(|pair| {
+                !::url::form_urlencoded::parse(pair.as_bytes())
+                    .next()
+                    .is_some_and(|(key, _)| key.starts_with("utm_"))
+            })
+            .collect();
+        if kept.iter().all(|pair| pair.is_empty()) {
+            normalized.set_query(None);
+        } else {
+            normalized.set_query(Some(&kept.join("&")));
+        }
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::api::v2::url::normalize;
+
+    #[test]
+    fn normalize_untouched() {
+        let url = Url::parse("https://example.com/a?b=c%20d").unwrap();
+        assert_eq!(normalize(&url), url);
+    }
+
+    #[test]
+    fn normalize_keeps_raw_pairs() {
+        let url = Url::parse("https://example.com/a?foo&utm_source=x&b=c%20d+e").unwrap();
+        assert_eq!(
+            normalize(&url).as_str(),
+            "https://example.com/a?foo&b=c%20d+e"
+        );
+    }
+
+    #[test]
+    fn normalize_tracking_only() {
+        let url = Url::parse("https://example.com/?utm_source=x&utm_medium=y").unwrap();
+        assert_eq!(normalize(&url).as_str(), "https://example.com/");
+    }
+}
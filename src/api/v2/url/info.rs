@@ -0,0 +1,131 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use crate::api::v2::url::normalize;
+use derive_builder::Builder;
+
+/// Create an Info endpoint for a URL.
+///
+/// Returns the title, number of saves and popular tags of the URL.
+///
+/// # Arguments
+/// * `url` - the URL to look up
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::url::Info;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// # use url::Url;
+/// let info_endpoint = Info::builder()
+///                     .normalized_url(&Url::parse("http://example.com/#top").unwrap())
+///                     .build().unwrap();
+/// assert_eq!(info_endpoint.endpoint(), "url");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+pub struct Info {
+    /// The URL to look up
+    url: ::url::Url,
+}
+
+impl InfoBuilder {
+    /// Look up the URL without its fragment and tracking parameters
+    pub fn normalized_url(&mut self, url: &::url::Url) -> &mut Self {
+        self.url = Some(normalize(url));
+        self
+    }
+}
+
+impl Info {
+    /// Create a builder for the endpoint
+    pub fn builder() -> InfoBuilder {
+        InfoBuilder::default()
+    }
+}
+
+impl Endpoint for Info {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "url".into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+
+        params.push("url", self.url.as_ref());
+
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use url::Url;
+
+    use crate::api::v2::url::Info;
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::UrlInfo;
+
+    #[test]
+    fn url_is_required() {
+        let err = Info::builder().build().unwrap_err();
+        assert_eq!(&err.to_string(), "`url` must be initialized")
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/url")
+            .add_query_params(&[("url", "https://example.com/#top")])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Info::builder()
+            .url(Url::parse("https://example.com/#top").unwrap())
+            .build()
+            .unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+
+    #[test]
+    fn endpoint_normalized() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/url")
+            .add_query_params(&[("url", "https://example.com/")])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!({
+                "url": "https://example.com/",
+                "title": "Example",
+                "saves": 42,
+                "popular_tags": [{"name": "example", "count": 30}],
+            }),
+        );
+
+        let info: UrlInfo = Info::builder()
+            .normalized_url(&Url::parse("https://example.com/?utm_campaign=x#top").unwrap())
+            .build()
+            .unwrap()
+            .query(&client)
+            .unwrap();
+        assert_eq!(info.saves, 42);
+        assert_eq!(info.popular_tags[0].name, "example");
+    }
+}
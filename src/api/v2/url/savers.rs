@@ -0,0 +1,184 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use crate::api::v2::url::normalize;
+use derive_builder::Builder;
+
+/// Create a Savers endpoint for a URL.
+///
+/// Returns the users who publicly saved the URL, and their tags for it.
+///
+/// # Arguments
+/// This builder requires the URL to look up.
+/// * `url` - the URL to look up
+///
+/// The results are paginated with two optional arguments.
+/// * `start` - offset value
+/// * `count` - number of results to return (up to 100)
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::url::Savers;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// # use url::Url;
+/// let savers_endpoint = Savers::builder()
+///                       .url(Url::parse("http://example.com/").unwrap())
+///                       .build().unwrap();
+/// assert_eq!(savers_endpoint.endpoint(), "url/savers");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
+pub struct Savers {
+    /// The URL to look up
+    url: ::url::Url,
+    /// Offset value (default is 0)
+    #[builder(default)]
+    start: Option<u64>,
+    /// Number of results to return
+    #[builder(default)]
+    count: Option<u8>,
+}
+
+impl SaversBuilder {
+    /// Look up the URL without its fragment and tracking parameters
+    pub fn normalized_url(&mut self, url: &::url::Url) -> &mut Self {
+        self.url = Some(normalize(url));
+        self
+    }
+
+    // Count is limited to 100.
+    fn validate(&self) -> Result<(), String> {
+        if let Some(Some(count)) = self.count {
+            if count > 100 {
+                return Err("Endpoint only accepts `count` of 100 or less".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Savers {
+    /// Create a builder for the endpoint
+    pub fn builder() -> SaversBuilder {
+        SaversBuilder::default()
+    }
+}
+
+impl Endpoint for Savers {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "url/savers".into()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        let mut params = QueryParams::default();
+
+        params
+            .push("url", self.url.as_ref())
+            .push_opt("start", self.start)
+            .push_opt("count", self.count);
+
+        params
+    }
+}
+
+impl Pageable for Savers {}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use url::Url;
+
+    use crate::api::v2::url::Savers;
+    use crate::api::{self, Pagination, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::UrlSavers;
+
+    #[test]
+    fn url_is_required() {
+        let err = Savers::builder().count(10).build().unwrap_err();
+        assert_eq!(&err.to_string(), "`url` must be initialized")
+    }
+
+    #[test]
+    fn endpoint_count_101() {
+        let err = Savers::builder()
+            .url(Url::parse("https://example.com/").unwrap())
+            .count(101)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint only accepts `count` of 100 or less"
+        )
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/url/savers")
+            .add_query_params(&[("url", "https://example.com/"), ("count", "5")])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!([{
+                "user": "someone",
+                "created_at": "2024-01-01T00:00:00Z",
+                "tags": ["example"],
+            }]),
+        );
+
+        let savers: UrlSavers = Savers::builder()
+            .url(Url::parse("https://example.com/").unwrap())
+            .count(5)
+            .build()
+            .unwrap()
+            .query(&client)
+            .unwrap();
+        assert_eq!(savers[0].user, "someone");
+    }
+
+    #[test]
+    fn paged() {
+        let endpoint = ExpectedUrl::builder()
+            .endpoint("v2/url/savers")
+            .add_query_params(&[
+                ("url", "https://example.com/"),
+                ("start", "0"),
+                ("count", "1"),
+            ])
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!([{
+                "user": "someone",
+                "created_at": "2024-01-01T00:00:00Z",
+                "tags": ["example"],
+            }]),
+        );
+
+        let endpoint = Savers::builder()
+            .url(Url::parse("https://example.com/").unwrap())
+            .build()
+            .unwrap();
+        let savers: UrlSavers = api::paged(endpoint, Pagination::Limit(1))
+            .query(&client)
+            .unwrap();
+        assert_eq!(savers[0].user, "someone");
+    }
+}
@@ -5,3 +5,10 @@
 // except according to those terms.
 
 //! Builders to interact with the set of user endpoints.
+mod profile;
+mod update;
+
+#[doc(inline)]
+pub use self::profile::Profile;
+#[doc(inline)]
+pub use self::update::Update;
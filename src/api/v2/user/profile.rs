@@ -0,0 +1,77 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create a Profile endpoint for the user.
+///
+/// This endpoint returns the profile and settings of the account.
+///
+/// # Arguments
+/// There are no arguments for this endpoint.
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::user::Profile;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let profile_endpoint = Profile::builder().build().unwrap();
+/// assert_eq!(profile_endpoint.endpoint(), "user");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Builder)]
+pub struct Profile {}
+
+impl Profile {
+    /// Create a builder for the endpoint
+    pub fn builder() -> ProfileBuilder {
+        ProfileBuilder::default()
+    }
+}
+
+impl Endpoint for Profile {
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "user".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::api::v2::user::Profile;
+    use crate::api::Query;
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+    use crate::types::v2::UserProfile;
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder().endpoint("v2/user").build().unwrap();
+        let client = SingleTestClient::new_json(
+            endpoint,
+            &json!({
+                "username": "someone",
+                "full_name": "Some One",
+                "created_at": "2012-03-04T00:00:00Z",
+                "private_by_default": true,
+                "public_profile": false,
+            }),
+        );
+
+        let profile: UserProfile = Profile::builder().build().unwrap().query(&client).unwrap();
+        assert_eq!(profile.username, "someone");
+        assert!(profile.private_by_default);
+    }
+}
@@ -0,0 +1,122 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::api::endpoint_prelude::*;
+use derive_builder::Builder;
+
+/// Create an Update endpoint for the user settings.
+///
+/// # Arguments
+/// This builder takes three optional arguments, at least one of which
+/// must be given.
+/// * `full_name` - the name shown on the profile
+/// * `private_by_default` - make new bookmarks private unless stated otherwise
+/// * `public_profile` - show the profile page to other users
+///
+/// # Example
+/// ```rust
+/// # fn main() {
+/// # use crate::pinboard_rs::api::v2::user::Update;
+/// # use crate::pinboard_rs::api::Endpoint;
+/// let update_endpoint = Update::builder().private_by_default(true).build().unwrap();
+/// assert_eq!(update_endpoint.endpoint(), "user");
+/// # }
+/// ```
+#[derive(Debug, Clone, Builder)]
+#[builder(setter(strip_option), build_fn(validate = "Self::validate"))]
+pub struct Update<'a> {
+    /// The name shown on the profile
+    #[builder(setter(into), default)]
+    full_name: Option<Cow<'a, str>>,
+    /// Make new bookmarks private by default
+    #[builder(default)]
+    private_by_default: Option<bool>,
+    /// Show the profile to other users
+    #[builder(default)]
+    public_profile: Option<bool>,
+}
+
+impl<'a> UpdateBuilder<'a> {
+    // Ensure there is something to update
+    fn validate(&self) -> Result<(), String> {
+        if matches!(self.full_name, Some(Some(_)))
+            || matches!(self.private_by_default, Some(Some(_)))
+            || matches!(self.public_profile, Some(Some(_)))
+        {
+            Ok(())
+        } else {
+            Err("Endpoint requires at least one setting to update".to_string())
+        }
+    }
+}
+
+impl<'a> Update<'a> {
+    /// Create a builder for the endpoint
+    pub fn builder() -> UpdateBuilder<'a> {
+        UpdateBuilder::default()
+    }
+}
+
+impl<'a> Endpoint for Update<'a> {
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn version(&self) -> ApiVersion {
+        ApiVersion::V2
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        "user".into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+        let mut params = FormParams::default();
+
+        params
+            .push_opt("full_name", self.full_name.as_ref())
+            .push_opt("private_by_default", self.private_by_default)
+            .push_opt("public_profile", self.public_profile);
+        params.into_body()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Method;
+
+    use crate::api::v2::user::Update;
+    use crate::api::{self, Query};
+    use crate::test::client::{ExpectedUrl, SingleTestClient};
+
+    #[test]
+    fn setting_is_required() {
+        let err = Update::builder().build().unwrap_err();
+        assert_eq!(
+            &err.to_string(),
+            "Endpoint requires at least one setting to update"
+        )
+    }
+
+    #[test]
+    fn endpoint() {
+        let endpoint = ExpectedUrl::builder()
+            .method(Method::POST)
+            .endpoint("v2/user")
+            .content_type("application/x-www-form-urlencoded")
+            .body_str("full_name=Some+One&public_profile=false")
+            .build()
+            .unwrap();
+        let client = SingleTestClient::new_raw(endpoint, "");
+
+        let endpoint = Update::builder()
+            .full_name("Some One")
+            .public_profile(false)
+            .build()
+            .unwrap();
+        api::ignore(endpoint).query(&client).unwrap();
+    }
+}
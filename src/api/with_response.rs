@@ -0,0 +1,149 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use async_trait::async_trait;
+use http::{HeaderMap, StatusCode};
+use serde::de::DeserializeOwned;
+
+use crate::api::{query, response, ApiError, AsyncClient, AsyncQuery, Client, Endpoint, Query};
+
+/// A query modifier that keeps the status and headers of the response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WithResponse<E> {
+    endpoint: E,
+}
+
+/// Return the status and headers of the response along with the data.
+pub fn with_response<E>(endpoint: E) -> WithResponse<E> {
+    WithResponse { endpoint }
+}
+
+/// Data from an endpoint with the status and headers it came with
+#[derive(Debug, Clone)]
+pub struct TypedResponse<T> {
+    /// The status of the response
+    pub status: StatusCode,
+    /// The headers of the response
+    pub headers: HeaderMap,
+    /// The deserialized data
+    pub data: T,
+}
+
+impl<T> TypedResponse<T> {
+    /// Discard the status and headers
+    pub fn into_data(self) -> T {
+        self.data
+    }
+}
+
+impl<E, T, C> Query<TypedResponse<T>, C> for WithResponse<E>
+where
+    E: Endpoint,
+    T: DeserializeOwned,
+    C: Client,
+{
+    fn query(&self, client: &C) -> Result<TypedResponse<T>, ApiError<C::Error>> {
+        query::send(&self.endpoint, client, |url, rsp| {
+            let data = response::decode(url, &rsp)?;
+            let (parts, _) = rsp.into_parts();
+
+            Ok(TypedResponse {
+                status: parts.status,
+                headers: parts.headers,
+                data,
+            })
+        })
+    }
+}
+
+#[async_trait]
+impl<E, T, C> AsyncQuery<TypedResponse<T>, C> for WithResponse<E>
+where
+    E: Endpoint + Sync,
+    T: DeserializeOwned + 'static,
+    C: AsyncClient + Sync,
+{
+    async fn query_async(&self, client: &C) -> Result<TypedResponse<T>, ApiError<C::Error>> {
+        query::send_async(&self.endpoint, client, |url, rsp| {
+            let data = response::decode(url, &rsp)?;
+            let (parts, _) = rsp.into_parts();
+
+            Ok(TypedResponse {
+                status: parts.status,
+                headers: parts.headers,
+                data,
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{header, StatusCode};
+    use serde_json::json;
+
+    use crate::api::{self, v1, v2, AsyncQuery, Query, TypedResponse};
+    use crate::mock::{Expectation, MockClient};
+    use crate::types::v1::PostsUpdate;
+    use crate::types::v2::TagList;
+
+    #[test]
+    fn headers_and_data() {
+        let client = MockClient::ordered();
+        client.expect(
+            Expectation::get("v1/posts/update")
+                .header("Date", "Mon, 01 Jan 2024 00:00:00 GMT")
+                .header("Cache-Control", "max-age=60")
+                .respond_json(&json!({"update_time": "2024-01-01T00:00:00Z"})),
+        );
+
+        let update = v1::posts::Update::builder().build().unwrap();
+        let rsp: TypedResponse<PostsUpdate> = api::with_response(update).query(&client).unwrap();
+        assert_eq!(rsp.status, StatusCode::OK);
+        assert_eq!(rsp.headers[header::CACHE_CONTROL], "max-age=60");
+        assert_eq!(rsp.headers[header::DATE], "Mon, 01 Jan 2024 00:00:00 GMT");
+        assert_eq!(
+            rsp.into_data().update_time.to_rfc3339(),
+            "2024-01-01T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn errors_are_kept() {
+        let client = MockClient::ordered();
+        client.expect(
+            Expectation::get("v1/posts/update")
+                .status(StatusCode::TOO_MANY_REQUESTS)
+                .header("Retry-After", "3")
+                .respond("Too Many Requests"),
+        );
+
+        let update = v1::posts::Update::builder().build().unwrap();
+        let err =
+            Query::<TypedResponse<PostsUpdate>, _>::query(&api::with_response(update), &client)
+                .unwrap_err();
+        assert!(err.is_rate_limited());
+        let context = err.context().unwrap();
+        assert_eq!(context.headers()[header::RETRY_AFTER], "3");
+    }
+
+    #[tokio::test]
+    async fn async_query() {
+        let client = MockClient::ordered();
+        client.expect(
+            Expectation::get("v2/tags")
+                .header("X-Request-Id", "abc")
+                .respond_json(&json!({"count": 0, "tags": []})),
+        );
+
+        let list = v2::tags::List::builder().build().unwrap();
+        let rsp: TypedResponse<TagList> =
+            api::with_response(list).query_async(&client).await.unwrap();
+        assert_eq!(rsp.headers["x-request-id"], "abc");
+        assert_eq!(rsp.data.count, 0);
+    }
+}
@@ -16,7 +16,8 @@ use http::Response as HttpResponse;
 use reqwest::Client as AsyncClient;
 use url::Url;
 
-use crate::pinboard::{PinboardResult, RestError};
+use crate::base::{base_url, endpoint_url, PinboardResult};
+use crate::pinboard::RestError;
 
 /// A representation of an asynchronous Pinboard API for a single user
 ///
@@ -47,12 +48,24 @@ impl AsyncPinboard {
         H: AsRef<str>,
         T: Into<String>,
     {
-        Self::new_impl(host.as_ref(), Auth::Token(token.into())).await
+        Self::new_impl("https", host.as_ref(), Auth::Token(token.into())).await
+    }
+
+    /// Create a new async Pinboard API representation which communicates over HTTP
+    ///
+    /// This is only meant for local servers, such as the one provided by the
+    /// `testing` feature. The token is sent in the clear.
+    pub async fn new_insecure<H, T>(host: H, token: T) -> PinboardResult<Self>
+    where
+        H: AsRef<str>,
+        T: Into<String>,
+    {
+        Self::new_impl("http", host.as_ref(), Auth::Token(token.into())).await
     }
 
     /// Internal method to create a new client
-    async fn new_impl(host: &str, auth: Auth) -> PinboardResult<Self> {
-        let url = Url::parse(&format!("https://{}/", host))?;
+    async fn new_impl(protocol: &str, host: &str, auth: Auth) -> PinboardResult<Self> {
+        let url = base_url(protocol, host)?;
         let client = AsyncClient::new();
         let api = AsyncPinboard { client, url, auth };
 
@@ -60,29 +73,36 @@ impl AsyncPinboard {
     }
 }
 
+impl AsyncPinboard {
+    /// Send a request with the token attached
+    pub(crate) async fn send(
+        &self,
+        mut request: http::Request<Vec<u8>>,
+    ) -> Result<HttpResponse<Bytes>, RestError> {
+        self.auth.set_header(request.headers_mut())?;
+        let request = request.try_into()?;
+        let rsp = self.client.execute(request).await?;
+
+        let mut http_rsp = HttpResponse::builder()
+            .status(rsp.status())
+            .version(rsp.version());
+        let headers = http_rsp.headers_mut().unwrap();
+        for (key, value) in rsp.headers() {
+            headers.insert(key, value.clone());
+        }
+        Ok(http_rsp.body(rsp.bytes().await?)?)
+    }
+}
+
 #[async_trait]
 impl api::AsyncClient for AsyncPinboard {
     /// Perform async reqwest query
     async fn rest_async(
         &self,
-        mut request: http::request::Builder,
+        request: http::request::Builder,
         body: Vec<u8>,
     ) -> Result<HttpResponse<Bytes>, api::ApiError<Self::Error>> {
-        let call = || async {
-            self.auth.set_header(request.headers_mut().unwrap())?;
-            let http_request = request.body(body)?;
-            let request = http_request.try_into()?;
-            let rsp = self.client.execute(request).await?;
-
-            let mut http_rsp = HttpResponse::builder()
-                .status(rsp.status())
-                .version(rsp.version());
-            let headers = http_rsp.headers_mut().unwrap();
-            for (key, value) in rsp.headers() {
-                headers.insert(key, value.clone());
-            }
-            Ok(http_rsp.body(rsp.bytes().await?)?)
-        };
+        let call = || async { self.send(request.body(body)?).await };
         call().map_err(api::ApiError::client).await
     }
 }
@@ -90,10 +110,11 @@ impl api::AsyncClient for AsyncPinboard {
 impl api::RestClient for AsyncPinboard {
     type Error = RestError;
 
-    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, api::ApiError<Self::Error>> {
-        let mut url = self.url.join(endpoint)?;
-        self.auth.add_to_url(&mut url);
-        url.query_pairs_mut().append_pair("format", "json");
-        Ok(url)
+    fn rest_endpoint(
+        &self,
+        version: api::ApiVersion,
+        endpoint: &str,
+    ) -> Result<Url, api::ApiError<Self::Error>> {
+        Ok(endpoint_url(&self.url, &self.auth, version, endpoint)?)
     }
 }
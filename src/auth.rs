@@ -6,6 +6,10 @@
 
 use http::{HeaderMap, HeaderValue};
 use thiserror::Error;
+use url::Url;
+
+/// The query parameter carrying the token of V1 requests
+const TOKEN_PARAM: &str = "auth_token";
 
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -45,12 +49,40 @@ impl Auth {
     }
 
     /// Add the token to a url
-    pub fn add_to_url(&self, url: &mut url::Url) {
+    pub fn add_to_url(&self, url: &mut Url) {
         match self {
             Auth::Token(token) => {
                 let mut pairs = url.query_pairs_mut();
-                pairs.append_pair("auth_token", token);
+                pairs.append_pair(TOKEN_PARAM, token);
             },
         }
     }
 }
+
+/// Remove the token from the query of a url
+///
+/// The token is replaced by `replacement`, or dropped along with its key
+/// if there is none. The query is left untouched if it has no token.
+/// Returns the token that was removed.
+pub(crate) fn redact_token(url: &mut Url, replacement: Option<&str>) -> Option<String> {
+    let mut token = None;
+    let pairs: Vec<_> = url
+        .query_pairs()
+        .into_owned()
+        .filter_map(|(key, value)| {
+            if key != TOKEN_PARAM {
+                return Some((key, value));
+            }
+            token = Some(value);
+            replacement.map(|replacement| (key, replacement.into()))
+        })
+        .collect();
+    if token.is_some() {
+        if pairs.is_empty() {
+            url.set_query(None);
+        } else {
+            url.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+    }
+    token
+}
@@ -0,0 +1,89 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Pieces shared by the clients of every HTTP backend
+
+use thiserror::Error;
+use url::Url;
+
+use crate::api;
+use crate::auth::{Auth, AuthError};
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum PinboardError {
+    #[error("failed to parse url: {}", source)]
+    UrlParse {
+        #[from]
+        source: url::ParseError,
+    },
+
+    #[error("error setting auth header: {}", source)]
+    AuthError {
+        #[from]
+        source: AuthError,
+    },
+}
+
+pub type PinboardResult<T> = Result<T, PinboardError>;
+
+/// The base URL of the API at `host`.
+pub(crate) fn base_url(protocol: &str, host: &str) -> PinboardResult<Url> {
+    Ok(Url::parse(&format!("{}://{}/", protocol, host))?)
+}
+
+/// Build the URL for an endpoint of the given API version.
+///
+/// V1 endpoints are joined onto the base URL (their paths carry the
+/// `v1/` prefix) and take the token and `format=json` as query
+/// parameters. V2 endpoints live under `v2/` and only use the
+/// `X-Auth-Token` header set on every request.
+pub(crate) fn endpoint_url(
+    base: &Url,
+    auth: &Auth,
+    version: api::ApiVersion,
+    endpoint: &str,
+) -> Result<Url, url::ParseError> {
+    match version {
+        api::ApiVersion::V2 => base.join("v2/")?.join(endpoint),
+        _ => {
+            let mut url = base.join(endpoint)?;
+            auth.add_to_url(&mut url);
+            url.query_pairs_mut().append_pair("format", "json");
+            Ok(url)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::api::ApiVersion;
+    use crate::auth::Auth;
+    use crate::base::endpoint_url;
+
+    fn base() -> Url {
+        Url::parse("https://api.pinboard.invalid/").unwrap()
+    }
+
+    #[test]
+    fn endpoint_url_v1() {
+        let auth = Auth::Token("user:TOKEN".into());
+        let url = endpoint_url(&base(), &auth, ApiVersion::V1, "v1/posts/all").unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://api.pinboard.invalid/v1/posts/all?auth_token=user%3ATOKEN&format=json"
+        );
+    }
+
+    #[test]
+    fn endpoint_url_v2() {
+        let auth = Auth::Token("user:TOKEN".into());
+        let url = endpoint_url(&base(), &auth, ApiVersion::V2, "tags/rename").unwrap();
+        assert_eq!(url.as_str(), "https://api.pinboard.invalid/v2/tags/rename");
+    }
+}
@@ -0,0 +1,689 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Response caching for clients
+//!
+//! [`CachingClient`] wraps another client and keeps successful `GET`
+//! responses for a time-to-live configured per endpoint. By default,
+//! `v1/posts/all` and `v1/posts/recent` are cached for as long as their
+//! rate limits forbid calling them again. Writes through the client
+//! clear the cache: the V1 write endpoints and every V2 request other
+//! than a `GET`. So does a change in the `update_time` reported by
+//! `v1/posts/update`. Expired responses are dropped whenever another is
+//! stored, unless they may still be revalidated.
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "reqwest")]
+//! # fn main() {
+//! use std::time::Duration;
+//!
+//! use pinboard_rs::api::{v1::posts, Query};
+//! use pinboard_rs::cache::CachingClient;
+//! use pinboard_rs::types::v1::Posts;
+//! use pinboard_rs::Pinboard;
+//!
+//! let pb = Pinboard::new("api.pinboard.in", "<TOKEN>").unwrap();
+//! let client = CachingClient::new(pb)
+//!     .ttl("v1/tags/get", Duration::from_secs(600))
+//!     .revalidate_with_update_time(true);
+//!
+//! let all = posts::All::builder().build().unwrap();
+//! let first: Posts = all.query(&client).unwrap();
+//! // Served from the cache.
+//! let second: Posts = all.query(&client).unwrap();
+//! # }
+//! # #[cfg(not(feature = "reqwest"))]
+//! # fn main() {}
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use http::request::Builder as RequestBuilder;
+use http::{HeaderMap, Method, Request, Response, StatusCode};
+use url::Url;
+
+use crate::api::{query, ApiError, ApiVersion, AsyncClient, Client, RestClient};
+use crate::types::v1::PostsUpdate;
+use crate::wrapper;
+
+const UPDATE_ENDPOINT: &str = "v1/posts/update";
+
+/// A cached response
+struct Entry {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+    stored_at: Instant,
+    ttl: Duration,
+    update_time: Option<DateTime<Utc>>,
+}
+
+impl Entry {
+    fn response(&self) -> Response<Bytes> {
+        let mut rsp = Response::new(self.body.clone());
+        *rsp.status_mut() = self.status;
+        *rsp.headers_mut() = self.headers.clone();
+        rsp
+    }
+
+    fn is_fresh(&self, now: Instant) -> bool {
+        now.duration_since(self.stored_at) < self.ttl
+    }
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<String, Entry>,
+    update_time: Option<DateTime<Utc>>,
+}
+
+impl CacheState {
+    /// Record the latest `update_time`, dropping entries older than it.
+    fn observe_update(&mut self, update_time: DateTime<Utc>) {
+        if self.update_time.is_some_and(|seen| seen != update_time) {
+            self.entries.clear();
+        }
+        self.update_time = Some(update_time);
+    }
+
+    /// Drop expired entries, unless they may still be revalidated.
+    fn prune(&mut self, now: Instant, revalidate: bool) {
+        self.entries
+            .retain(|_, entry| entry.is_fresh(now) || (revalidate && entry.update_time.is_some()));
+    }
+}
+
+/// What the cache has for a request
+enum Lookup {
+    Hit(Response<Bytes>),
+    Stale,
+    Miss,
+}
+
+/// The parts of a request the cache looks at
+struct RequestInfo {
+    method: Method,
+    /// The path without the leading slash, e.g. `v1/posts/all`
+    path: String,
+    /// The full URL, used as the cache key
+    key: String,
+}
+
+impl RequestInfo {
+    fn new(request: &RequestBuilder) -> Self {
+        let method = request.method_ref().cloned().unwrap_or_default();
+        let key = request
+            .uri_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        let path = wrapper::request_path(request);
+
+        RequestInfo { method, path, key }
+    }
+}
+
+/// A client which caches responses from another client
+pub struct CachingClient<C> {
+    inner: C,
+    ttls: HashMap<String, Duration>,
+    default_ttl: Option<Duration>,
+    invalidate_on: HashSet<String>,
+    revalidate: bool,
+    state: Mutex<CacheState>,
+}
+
+impl<C> CachingClient<C> {
+    /// Cache responses from `inner` with the default settings.
+    pub fn new(inner: C) -> Self {
+        let invalidate_on = [
+            "v1/posts/add",
+            "v1/posts/delete",
+            "v1/tags/rename",
+            "v1/tags/delete",
+        ];
+
+        CachingClient {
+            inner,
+            ttls: wrapper::endpoint_intervals()
+                .into_iter()
+                .map(|(path, ttl)| (path.into(), ttl))
+                .collect(),
+            default_ttl: None,
+            invalidate_on: invalidate_on.into_iter().map(Into::into).collect(),
+            revalidate: false,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Cache responses from `endpoint` for `ttl`.
+    ///
+    /// Endpoints are given by their path, e.g. `v1/tags/get` or `v2/tags`.
+    /// A zero TTL disables caching for the endpoint.
+    pub fn ttl<E>(mut self, endpoint: E, ttl: Duration) -> Self
+    where
+        E: Into<String>,
+    {
+        self.ttls.insert(endpoint.into(), ttl);
+        self
+    }
+
+    /// Cache responses from endpoints without their own TTL for `ttl`.
+    pub fn default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+
+    /// Clear the cache after successful requests to `endpoint`.
+    ///
+    /// The V1 write endpoints are included by default; requests with any
+    /// method other than `GET` always clear the cache.
+    pub fn invalidate_on<E>(mut self, endpoint: E) -> Self
+    where
+        E: Into<String>,
+    {
+        self.invalidate_on.insert(endpoint.into());
+        self
+    }
+
+    /// Check `v1/posts/update` before discarding an expired response.
+    ///
+    /// If the bookmarks have not changed since the response was stored,
+    /// it is kept for another TTL instead of being fetched again. The
+    /// update time is fetched before the first response is stored, and
+    /// if checking it fails the response is fetched again.
+    pub fn revalidate_with_update_time(mut self, revalidate: bool) -> Self {
+        self.revalidate = revalidate;
+        self
+    }
+
+    /// Drop every cached response.
+    pub fn invalidate(&self) {
+        self.lock().entries.clear();
+    }
+
+    /// The number of cached responses.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The inner client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheState> {
+        wrapper::lock(&self.state)
+    }
+
+    fn ttl_for(&self, path: &str) -> Option<Duration> {
+        self.ttls
+            .get(path)
+            .copied()
+            .or(self.default_ttl)
+            .filter(|ttl| !ttl.is_zero())
+    }
+
+    fn lookup(&self, info: &RequestInfo) -> Lookup {
+        if info.method != Method::GET || self.ttl_for(&info.path).is_none() {
+            return Lookup::Miss;
+        }
+
+        let state = self.lock();
+        match state.entries.get(&info.key) {
+            Some(entry) if entry.is_fresh(Instant::now()) => Lookup::Hit(entry.response()),
+            Some(entry) if self.revalidate && entry.update_time.is_some() => Lookup::Stale,
+            _ => Lookup::Miss,
+        }
+    }
+
+    /// Whether the update time must be fetched before storing a response.
+    fn needs_update_time(&self, info: &RequestInfo) -> bool {
+        self.revalidate
+            && info.method == Method::GET
+            && info.path != UPDATE_ENDPOINT
+            && self.ttl_for(&info.path).is_some()
+            && self.lock().update_time.is_none()
+    }
+
+    /// Keep a stale entry if the bookmarks have not changed since it was stored.
+    fn renew(&self, info: &RequestInfo, update_time: DateTime<Utc>) -> Option<Response<Bytes>> {
+        let mut state = self.lock();
+        state.observe_update(update_time);
+        let entry = state.entries.get_mut(&info.key)?;
+        if entry.update_time != Some(update_time) {
+            return None;
+        }
+        entry.stored_at = Instant::now();
+        Some(entry.response())
+    }
+
+    fn record(&self, info: RequestInfo, rsp: &Response<Bytes>) {
+        if !rsp.status().is_success() {
+            return;
+        }
+
+        let mut state = self.lock();
+        if info.method != Method::GET || self.invalidate_on.contains(&info.path) {
+            state.entries.clear();
+            return;
+        }
+        if info.path == UPDATE_ENDPOINT {
+            if let Ok(update) = serde_json::from_slice::<PostsUpdate>(rsp.body()) {
+                state.observe_update(update.update_time);
+            }
+        }
+
+        let ttl = self
+            .ttl_for(&info.path)
+            .filter(|_| info.method == Method::GET);
+        if let Some(ttl) = ttl {
+            let now = Instant::now();
+            state.prune(now, self.revalidate);
+            let entry = Entry {
+                status: rsp.status(),
+                headers: rsp.headers().clone(),
+                body: rsp.body().clone(),
+                stored_at: now,
+                ttl,
+                update_time: state.update_time,
+            };
+            state.entries.insert(info.key, entry);
+        }
+    }
+}
+
+impl<C> CachingClient<C>
+where
+    C: RestClient,
+{
+    fn update_request(&self) -> Result<RequestBuilder, ApiError<C::Error>> {
+        let url: Url = self.inner.rest_endpoint(ApiVersion::V1, UPDATE_ENDPOINT)?;
+        Ok(Request::builder()
+            .method(Method::GET)
+            .uri(query::url_to_http_uri(&url)))
+    }
+
+    fn update_time(rsp: &Result<Response<Bytes>, ApiError<C::Error>>) -> Option<DateTime<Utc>> {
+        let rsp = rsp.as_ref().ok().filter(|rsp| rsp.status().is_success())?;
+        serde_json::from_slice::<PostsUpdate>(rsp.body())
+            .ok()
+            .map(|update| update.update_time)
+    }
+}
+
+impl<C> RestClient for CachingClient<C>
+where
+    C: RestClient,
+{
+    type Error = C::Error;
+
+    fn rest_endpoint(
+        &self,
+        version: ApiVersion,
+        endpoint: &str,
+    ) -> Result<Url, ApiError<Self::Error>> {
+        self.inner.rest_endpoint(version, endpoint)
+    }
+}
+
+impl<C> Client for CachingClient<C>
+where
+    C: Client,
+{
+    fn rest(
+        &self,
+        request: RequestBuilder,
+        body: Vec<u8>,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let info = RequestInfo::new(&request);
+        let lookup = self.lookup(&info);
+        if let Lookup::Hit(rsp) = lookup {
+            return Ok(rsp);
+        }
+        if matches!(lookup, Lookup::Stale) || self.needs_update_time(&info) {
+            // A failed check falls through to fetching the request itself.
+            let update = self
+                .update_request()
+                .and_then(|update| self.inner.rest(update, Vec::new()));
+            if let Some(update_time) = Self::update_time(&update) {
+                if let Some(rsp) = self.renew(&info, update_time) {
+                    return Ok(rsp);
+                }
+            }
+        }
+
+        let rsp = self.inner.rest(request, body)?;
+        self.record(info, &rsp);
+        Ok(rsp)
+    }
+}
+
+#[async_trait]
+impl<C> AsyncClient for CachingClient<C>
+where
+    C: AsyncClient + Sync,
+{
+    async fn rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Vec<u8>,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let info = RequestInfo::new(&request);
+        let lookup = self.lookup(&info);
+        if let Lookup::Hit(rsp) = lookup {
+            return Ok(rsp);
+        }
+        if matches!(lookup, Lookup::Stale) || self.needs_update_time(&info) {
+            // A failed check falls through to fetching the request itself.
+            let update = match self.update_request() {
+                Ok(update) => self.inner.rest_async(update, Vec::new()).await,
+                Err(err) => Err(err),
+            };
+            if let Some(update_time) = Self::update_time(&update) {
+                if let Some(rsp) = self.renew(&info, update_time) {
+                    return Ok(rsp);
+                }
+            }
+        }
+
+        let rsp = self.inner.rest_async(request, body).await?;
+        self.record(info, &rsp);
+        Ok(rsp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    use bytes::Bytes;
+    use http::request::Builder as RequestBuilder;
+    use http::{Response, StatusCode};
+    use serde_json::json;
+    use url::Url;
+
+    use crate::api::{self, v1, v2, ApiError, ApiVersion, AsyncQuery, Client, Query, RestClient};
+    use crate::cache::CachingClient;
+    use crate::mock::{Expectation, MockClient, MockClientError};
+    use crate::types::v1::{Posts, PostsUpdate};
+    use crate::types::v2::TagList;
+
+    fn all() -> v1::posts::All<'static> {
+        v1::posts::All::builder().build().unwrap()
+    }
+
+    fn update(time: &str) -> Expectation {
+        Expectation::get("v1/posts/update").respond_json(&json!({ "update_time": time }))
+    }
+
+    // A mock client whose `posts/update` calls can be made to fail.
+    struct FlakyUpdates {
+        mock: MockClient,
+        fail: AtomicBool,
+    }
+
+    impl RestClient for FlakyUpdates {
+        type Error = MockClientError;
+
+        fn rest_endpoint(
+            &self,
+            version: ApiVersion,
+            endpoint: &str,
+        ) -> Result<Url, ApiError<Self::Error>> {
+            self.mock.rest_endpoint(version, endpoint)
+        }
+    }
+
+    impl Client for FlakyUpdates {
+        fn rest(
+            &self,
+            request: RequestBuilder,
+            body: Vec<u8>,
+        ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+            let path = request.uri_ref().unwrap().path();
+            if path.ends_with("v1/posts/update") && self.fail.load(Ordering::SeqCst) {
+                return Err(url::ParseError::EmptyHost.into());
+            }
+            self.mock.rest(request, body)
+        }
+    }
+
+    #[test]
+    fn caches_by_default_ttl() {
+        let mock = MockClient::ordered();
+        mock.expect(Expectation::get("v1/posts/all").respond_json(&json!([])));
+        let client = CachingClient::new(mock);
+
+        let first: Posts = all().query(&client).unwrap();
+        let second: Posts = all().query(&client).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(client.len(), 1);
+    }
+
+    #[test]
+    fn parameters_are_part_of_the_key() {
+        let mock = MockClient::ordered();
+        mock.expect(
+            Expectation::get("v1/posts/recent")
+                .query("count", "5")
+                .respond_json(&json!({"date": "2024-01-01T00:00:00Z", "user": "u", "posts": []})),
+        )
+        .expect(
+            Expectation::get("v1/posts/recent")
+                .query("count", "6")
+                .respond_json(&json!({"date": "2024-01-01T00:00:00Z", "user": "u", "posts": []})),
+        );
+        let client = CachingClient::new(mock);
+
+        for count in [5, 6, 5, 6] {
+            let recent = v1::posts::Recent::builder().count(count).build().unwrap();
+            api::ignore(recent).query(&client).unwrap();
+        }
+    }
+
+    #[test]
+    fn expired_entries_are_pruned() {
+        let mock = MockClient::ordered();
+        for count in 1..=5 {
+            mock.expect(
+                Expectation::get("v1/posts/recent")
+                    .query("count", count.to_string())
+                    .respond_json(
+                        &json!({"date": "2024-01-01T00:00:00Z", "user": "u", "posts": []}),
+                    ),
+            );
+        }
+        let client = CachingClient::new(mock).ttl("v1/posts/recent", Duration::from_nanos(1));
+
+        for count in 1..=5 {
+            let recent = v1::posts::Recent::builder().count(count).build().unwrap();
+            api::ignore(recent).query(&client).unwrap();
+            assert_eq!(client.len(), 1);
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn uncached_endpoints() {
+        let mock = MockClient::ordered();
+        mock.expect(Expectation::get("v1/tags/get").respond_json(&json!({})))
+            .expect(Expectation::get("v1/tags/get").respond_json(&json!({})));
+        let client = CachingClient::new(mock);
+
+        let get = || v1::tags::Get::builder().build().unwrap();
+        api::ignore(get()).query(&client).unwrap();
+        api::ignore(get()).query(&client).unwrap();
+        assert!(client.is_empty());
+    }
+
+    #[test]
+    fn errors_are_not_cached() {
+        let mock = MockClient::ordered();
+        mock.expect(Expectation::get("v1/posts/all").status(StatusCode::BAD_GATEWAY))
+            .expect(Expectation::get("v1/posts/all").respond_json(&json!([])));
+        let client = CachingClient::new(mock);
+
+        assert!(api::ignore(all()).query(&client).is_err());
+        api::ignore(all()).query(&client).unwrap();
+    }
+
+    #[test]
+    fn writes_invalidate() {
+        let mock = MockClient::ordered();
+        mock.expect(Expectation::get("v1/posts/all").respond_json(&json!([])))
+            .expect(Expectation::get("v1/tags/rename").respond_json(&json!({"result": "done"})))
+            .expect(Expectation::get("v1/posts/all").respond_json(&json!([])));
+        let client = CachingClient::new(mock);
+
+        api::ignore(all()).query(&client).unwrap();
+        let rename = v1::tags::Rename::builder()
+            .old("a")
+            .new("b")
+            .build()
+            .unwrap();
+        api::ignore(rename).query(&client).unwrap();
+        assert!(client.is_empty());
+        api::ignore(all()).query(&client).unwrap();
+    }
+
+    #[test]
+    fn add_invalidates() {
+        let mock = MockClient::ordered();
+        mock.expect(Expectation::get("v1/posts/all").respond_json(&json!([])))
+            .expect(Expectation::get("v1/posts/add").respond_json(&json!({"result_code": "done"})));
+        let client = CachingClient::new(mock);
+
+        api::ignore(all()).query(&client).unwrap();
+        let add = v1::posts::Add::builder()
+            .url(Url::parse("https://example.com/").unwrap())
+            .description("Example")
+            .build()
+            .unwrap();
+        api::ignore(add).query(&client).unwrap();
+        assert!(client.is_empty());
+    }
+
+    #[test]
+    fn v2_writes_invalidate() {
+        let mock = MockClient::ordered();
+        mock.expect(Expectation::get("v2/tags").respond_json(&json!({"count": 0, "tags": []})))
+            .expect(
+                Expectation::post("v2/tags/rename")
+                    .respond_json(&json!({"tags": ["b"], "bookmarks": 1})),
+            )
+            .expect(Expectation::get("v2/tags").respond_json(&json!({"count": 0, "tags": []})));
+        let client = CachingClient::new(mock).ttl("v2/tags", Duration::from_secs(60));
+
+        let list = || v2::tags::List::builder().build().unwrap();
+        api::ignore(list()).query(&client).unwrap();
+        let rename = v2::tags::Rename::builder()
+            .old("a")
+            .new("b")
+            .build()
+            .unwrap();
+        api::ignore(rename).query(&client).unwrap();
+        assert!(client.is_empty());
+        api::ignore(list()).query(&client).unwrap();
+    }
+
+    #[test]
+    fn update_time_change_invalidates() {
+        let mock = MockClient::ordered();
+        mock.expect(update("2024-01-01T00:00:00Z"))
+            .expect(Expectation::get("v1/posts/all").respond_json(&json!([])))
+            .expect(update("2024-01-02T00:00:00Z"))
+            .expect(Expectation::get("v1/posts/all").respond_json(&json!([])));
+        let client = CachingClient::new(mock);
+
+        let update = || v1::posts::Update::builder().build().unwrap();
+        let _: PostsUpdate = update().query(&client).unwrap();
+        api::ignore(all()).query(&client).unwrap();
+        let _: PostsUpdate = update().query(&client).unwrap();
+        assert!(client.is_empty());
+        api::ignore(all()).query(&client).unwrap();
+    }
+
+    #[test]
+    fn revalidate_with_update_time() {
+        let mock = MockClient::ordered();
+        mock.expect(update("2024-01-01T00:00:00Z"))
+            .expect(Expectation::get("v1/posts/all").respond_json(&json!([])))
+            // Expired but unchanged: kept.
+            .expect(update("2024-01-01T00:00:00Z"))
+            // Expired and changed: fetched again.
+            .expect(update("2024-01-02T00:00:00Z"))
+            .expect(Expectation::get("v1/posts/all").respond_json(&json!([])));
+        let client = CachingClient::new(mock)
+            .ttl("v1/posts/all", Duration::from_nanos(1))
+            .revalidate_with_update_time(true);
+
+        let update = v1::posts::Update::builder().build().unwrap();
+        let _: PostsUpdate = update.query(&client).unwrap();
+        api::ignore(all()).query(&client).unwrap();
+        std::thread::sleep(Duration::from_millis(1));
+        api::ignore(all()).query(&client).unwrap();
+        std::thread::sleep(Duration::from_millis(1));
+        api::ignore(all()).query(&client).unwrap();
+    }
+
+    #[test]
+    fn revalidate_fetches_update_time() {
+        let mock = MockClient::ordered();
+        mock.expect(update("2024-01-01T00:00:00Z"))
+            .expect(Expectation::get("v1/posts/all").respond_json(&json!([])))
+            .expect(update("2024-01-01T00:00:00Z"));
+        let client = CachingClient::new(mock)
+            .ttl("v1/posts/all", Duration::from_nanos(1))
+            .revalidate_with_update_time(true);
+
+        api::ignore(all()).query(&client).unwrap();
+        std::thread::sleep(Duration::from_millis(1));
+        api::ignore(all()).query(&client).unwrap();
+    }
+
+    #[test]
+    fn failed_revalidation_fetches_again() {
+        let mock = MockClient::ordered();
+        mock.expect(update("2024-01-01T00:00:00Z"))
+            .expect(Expectation::get("v1/posts/all").respond_json(&json!([])))
+            .expect(Expectation::get("v1/posts/all").respond_json(&json!([])));
+        let flaky = FlakyUpdates {
+            mock,
+            fail: AtomicBool::new(false),
+        };
+        let client = CachingClient::new(flaky)
+            .ttl("v1/posts/all", Duration::from_nanos(1))
+            .revalidate_with_update_time(true);
+
+        api::ignore(all()).query(&client).unwrap();
+        client.inner().fail.store(true, Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(1));
+        api::ignore(all()).query(&client).unwrap();
+    }
+
+    #[tokio::test]
+    async fn async_client() {
+        let mock = MockClient::ordered();
+        mock.expect(Expectation::get("v2/tags").respond_json(&json!({"count": 0, "tags": []})));
+        let client = CachingClient::new(mock).ttl("v2/tags", Duration::from_secs(60));
+
+        let list = v2::tags::List::builder().build().unwrap();
+        let _: TagList = list.query_async(&client).await.unwrap();
+        let tags: TagList = list.query_async(&client).await.unwrap();
+        assert_eq!(tags.count, 0);
+    }
+}
@@ -0,0 +1,752 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Record and replay HTTP interactions
+//!
+//! [`CassetteClient`] wraps another client. When recording, requests go
+//! to the inner client and each request and response is written to a
+//! cassette file with the API token, and any `password` or `token` JSON
+//! fields, removed. When replaying, responses are served from the
+//! cassette without touching the network. It is available with the
+//! `cassette` feature.
+//!
+//! The clients in this crate add the `X-Auth-Token` header of V2 requests
+//! after the cassette sees the request, so pass the token with
+//! [`CassetteClient::with_token`] to have it removed from V2 bodies too.
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "reqwest")]
+//! # fn main() {
+//! use pinboard_rs::api::{v1::posts, Query};
+//! use pinboard_rs::cassette::{CassetteClient, CassetteMode};
+//! use pinboard_rs::types::v1::PostsUpdate;
+//! use pinboard_rs::Pinboard;
+//!
+//! let mode = if std::env::var_os("PINBOARD_RECORD").is_some() {
+//!     CassetteMode::Record
+//! } else {
+//!     CassetteMode::Replay
+//! };
+//! let token = std::env::var("PINBOARD_TOKEN").unwrap_or_default();
+//! let inner = Pinboard::new("api.pinboard.in", &token).unwrap();
+//! let client = CassetteClient::new(inner, "tests/cassettes/update.json", mode)
+//!     .unwrap()
+//!     .with_token(token);
+//!
+//! let update = posts::Update::builder().build().unwrap();
+//! let _: PostsUpdate = update.query(&client).unwrap();
+//! # }
+//! # #[cfg(not(feature = "reqwest"))]
+//! # fn main() {}
+//! ```
+
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use bytes::Bytes;
+use http::request::Builder as RequestBuilder;
+use http::{HeaderName, Method, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use url::Url;
+
+use crate::api::{ApiError, ApiVersion, AsyncClient, Client, RestClient};
+use crate::auth;
+use crate::wrapper;
+
+const REDACTED: &str = "REDACTED";
+const AUTH_HEADER: HeaderName = HeaderName::from_static("x-auth-token");
+// JSON fields whose values are always removed from recorded bodies
+const SECRET_FIELDS: &[&str] = &["password", "token"];
+
+/// Whether a [`CassetteClient`] records or replays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Send requests to the inner client and save them to the cassette.
+    Record,
+    /// Serve responses from the cassette.
+    Replay,
+}
+
+/// Errors from a [`CassetteClient`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum CassetteError<E>
+where
+    E: Error + Send + Sync + 'static,
+{
+    /// The inner client failed
+    #[error("{}", source)]
+    Client {
+        /// The inner client error.
+        source: E,
+    },
+
+    /// The cassette could not be written
+    #[error("failed to write cassette: {}", source)]
+    Io {
+        /// The source of the error.
+        source: io::Error,
+    },
+
+    /// The cassette could not be serialized
+    #[error("failed to serialize cassette: {}", source)]
+    Serialize {
+        /// The source of the error.
+        source: serde_json::Error,
+    },
+
+    /// No recorded interaction matched the request
+    #[error("no recorded interaction for `{} {}`", method, url)]
+    NoInteraction {
+        /// The method of the request.
+        method: Method,
+        /// The URL of the request, with the token removed.
+        url: String,
+    },
+}
+
+/// A recorded body, stored as text when it is UTF-8 and as base64 otherwise
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Body {
+    Text(String),
+    Binary { base64: String },
+}
+
+impl Default for Body {
+    fn default() -> Self {
+        Body::Text(String::new())
+    }
+}
+
+impl Body {
+    fn is_empty(&self) -> bool {
+        matches!(self, Body::Text(text) if text.is_empty())
+    }
+
+    fn to_bytes(&self) -> Bytes {
+        match self {
+            Body::Text(text) => Bytes::from(text.clone()),
+            Body::Binary { base64 } => BASE64.decode(base64).unwrap_or_default().into(),
+        }
+    }
+}
+
+/// A recorded request and its response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Body::is_empty")]
+    request_body: Body,
+    status: u16,
+    #[serde(default)]
+    headers: Vec<(String, String)>,
+    body: Body,
+    #[serde(skip)]
+    used: bool,
+}
+
+impl Interaction {
+    fn matches(&self, method: &Method, path: &str, query: &[(String, String)]) -> bool {
+        self.method == method.as_str() && self.path == path && self.query == query
+    }
+
+    fn response(&self) -> Response<Bytes> {
+        let mut rsp =
+            Response::builder().status(StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK));
+        for (name, value) in &self.headers {
+            rsp = rsp.header(name.as_str(), value.as_str());
+        }
+        rsp.body(self.body.to_bytes())
+            .unwrap_or_else(|_| Response::new(self.body.to_bytes()))
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Cassette {
+    interactions: Vec<Interaction>,
+}
+
+// The parts of a request used for matching, with the token split out.
+struct RequestKey {
+    method: Method,
+    path: String,
+    query: Vec<(String, String)>,
+    token: Option<String>,
+}
+
+impl RequestKey {
+    fn new(request: &RequestBuilder, known_token: Option<&str>) -> Self {
+        let method = request.method_ref().cloned().unwrap_or_default();
+        let url = request
+            .uri_ref()
+            .and_then(|uri| Url::parse(&uri.to_string()).ok());
+
+        let (path, mut query, token) = match url {
+            Some(mut url) => {
+                let token = auth::redact_token(&mut url, None);
+                let query = url.query_pairs().into_owned().collect();
+                (url.path().into(), query, token)
+            },
+            None => (String::new(), Vec::new(), None),
+        };
+        query.sort();
+        let token = token
+            .or_else(|| {
+                let value = request.headers_ref()?.get(AUTH_HEADER)?;
+                value.to_str().ok().map(Into::into)
+            })
+            .or_else(|| known_token.map(Into::into));
+
+        RequestKey {
+            method,
+            path,
+            query,
+            token,
+        }
+    }
+
+    // The token and its secret half, which is returned by `user/api_token`.
+    fn secrets(&self) -> impl Iterator<Item = &str> {
+        let token = self.token.as_deref();
+        let secret = token.and_then(|token| token.split(':').nth(1));
+        token
+            .into_iter()
+            .chain(secret)
+            .filter(|value| !value.is_empty())
+    }
+
+    fn redact(&self, data: &[u8]) -> Body {
+        let text = match std::str::from_utf8(data) {
+            Ok(text) => text,
+            Err(_) => {
+                let mut data = data.to_vec();
+                for secret in self.secrets() {
+                    data = replace_bytes(&data, secret.as_bytes(), REDACTED.as_bytes());
+                }
+                return Body::Binary {
+                    base64: BASE64.encode(data),
+                };
+            },
+        };
+
+        let mut text = text.to_string();
+        if let Ok(mut value) = serde_json::from_str::<Value>(&text) {
+            if redact_fields(&mut value) {
+                text = value.to_string();
+            }
+        }
+        for secret in self.secrets() {
+            text = text.replace(secret, REDACTED);
+        }
+        Body::Text(text)
+    }
+
+    fn url(&self) -> String {
+        let query: Vec<_> = self
+            .query
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        if query.is_empty() {
+            self.path.clone()
+        } else {
+            format!("{}?{}", self.path, query.join("&"))
+        }
+    }
+}
+
+// Replace the values of secret JSON fields, returning whether any were found.
+fn redact_fields(value: &mut Value) -> bool {
+    match value {
+        Value::Object(map) => {
+            let mut found = false;
+            for (key, value) in map.iter_mut() {
+                if SECRET_FIELDS.contains(&key.as_str()) && !value.is_null() {
+                    *value = Value::String(REDACTED.into());
+                    found = true;
+                } else {
+                    found |= redact_fields(value);
+                }
+            }
+            found
+        },
+        Value::Array(values) => {
+            let mut found = false;
+            for value in values {
+                found |= redact_fields(value);
+            }
+            found
+        },
+        _ => false,
+    }
+}
+
+fn replace_bytes(data: &[u8], from: &[u8], to: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut rest = data;
+    while !rest.is_empty() {
+        if rest.starts_with(from) {
+            out.extend_from_slice(to);
+            rest = &rest[from.len()..];
+        } else {
+            out.push(rest[0]);
+            rest = &rest[1..];
+        }
+    }
+    out
+}
+
+/// A client which records interactions to, or replays them from, a cassette
+///
+/// Requests are matched on their method, path and query parameters. Each
+/// recorded interaction is served once, in recording order; when all
+/// matching interactions have been served, the last one is repeated.
+pub struct CassetteClient<C> {
+    inner: C,
+    mode: CassetteMode,
+    path: PathBuf,
+    token: Option<String>,
+    cassette: Mutex<Cassette>,
+}
+
+impl<C> CassetteClient<C>
+where
+    C: RestClient,
+{
+    /// Create a client using the cassette at `path`.
+    ///
+    /// Recording starts a new cassette, replacing any existing file;
+    /// replaying reads the cassette immediately.
+    pub fn new<P>(inner: C, path: P, mode: CassetteMode) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        let cassette = match mode {
+            CassetteMode::Record => Cassette::default(),
+            CassetteMode::Replay => serde_json::from_slice(&fs::read(&path)?)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+        };
+
+        Ok(CassetteClient {
+            inner,
+            mode,
+            path,
+            token: None,
+            cassette: Mutex::new(cassette),
+        })
+    }
+
+    /// Remove `token` from recorded bodies.
+    ///
+    /// Tokens passed as the `auth_token` query parameter or the
+    /// `X-Auth-Token` header are found without this.
+    pub fn with_token<T>(mut self, token: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Create a client recording to `path`.
+    pub fn record<P>(inner: C, path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Self::new(inner, path, CassetteMode::Record)
+    }
+
+    /// Create a client replaying from `path`.
+    pub fn replay<P>(inner: C, path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Self::new(inner, path, CassetteMode::Replay)
+    }
+
+    /// The mode of the client.
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// The inner client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Cassette> {
+        wrapper::lock(&self.cassette)
+    }
+
+    fn play(&self, key: &RequestKey) -> Result<Response<Bytes>, ApiError<CassetteError<C::Error>>> {
+        let mut cassette = self.lock();
+        let interactions = &mut cassette.interactions;
+        let matching = |i: &&mut Interaction| i.matches(&key.method, &key.path, &key.query);
+
+        let interaction = match interactions.iter_mut().filter(|i| !i.used).find(matching) {
+            Some(interaction) => Some(interaction),
+            None => interactions.iter_mut().rev().find(matching),
+        };
+        match interaction {
+            Some(interaction) => {
+                interaction.used = true;
+                Ok(interaction.response())
+            },
+            None => Err(ApiError::client(CassetteError::NoInteraction {
+                method: key.method.clone(),
+                url: key.url(),
+            })),
+        }
+    }
+
+    fn save(
+        &self,
+        key: RequestKey,
+        body: &[u8],
+        rsp: &Response<Bytes>,
+    ) -> Result<(), ApiError<CassetteError<C::Error>>> {
+        let headers = rsp
+            .headers()
+            .iter()
+            .filter(|(name, _)| *name != http::header::SET_COOKIE && *name != AUTH_HEADER)
+            .filter_map(|(name, value)| Some((name.as_str().into(), value.to_str().ok()?.into())))
+            .collect();
+        let interaction = Interaction {
+            method: key.method.as_str().into(),
+            request_body: key.redact(body),
+            status: rsp.status().as_u16(),
+            headers,
+            body: key.redact(rsp.body()),
+            path: key.path,
+            query: key.query,
+            used: false,
+        };
+
+        let mut cassette = self.lock();
+        cassette.interactions.push(interaction);
+        let data = serde_json::to_vec_pretty(&*cassette)
+            .map_err(|source| ApiError::client(CassetteError::Serialize { source }))?;
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|source| ApiError::client(CassetteError::Io { source }))?;
+        }
+        fs::write(&self.path, data).map_err(|source| ApiError::client(CassetteError::Io { source }))
+    }
+}
+
+impl<C> RestClient for CassetteClient<C>
+where
+    C: RestClient,
+{
+    type Error = CassetteError<C::Error>;
+
+    fn rest_endpoint(
+        &self,
+        version: ApiVersion,
+        endpoint: &str,
+    ) -> Result<Url, ApiError<Self::Error>> {
+        self.inner
+            .rest_endpoint(version, endpoint)
+            .map_err(|err| err.map_client(|source| CassetteError::Client { source }))
+    }
+}
+
+impl<C> Client for CassetteClient<C>
+where
+    C: Client,
+{
+    fn rest(
+        &self,
+        request: RequestBuilder,
+        body: Vec<u8>,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let key = RequestKey::new(&request, self.token.as_deref());
+        match self.mode {
+            CassetteMode::Replay => self.play(&key),
+            CassetteMode::Record => {
+                let rsp = self
+                    .inner
+                    .rest(request, body.clone())
+                    .map_err(|err| err.map_client(|source| CassetteError::Client { source }))?;
+                self.save(key, &body, &rsp)?;
+                Ok(rsp)
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl<C> AsyncClient for CassetteClient<C>
+where
+    C: AsyncClient + Sync,
+{
+    async fn rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Vec<u8>,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let key = RequestKey::new(&request, self.token.as_deref());
+        match self.mode {
+            CassetteMode::Replay => self.play(&key),
+            CassetteMode::Record => {
+                let rsp = self
+                    .inner
+                    .rest_async(request, body.clone())
+                    .await
+                    .map_err(|err| err.map_client(|source| CassetteError::Client { source }))?;
+                self.save(key, &body, &rsp)?;
+                Ok(rsp)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use http::request::Builder as RequestBuilder;
+    use http::{Response, StatusCode};
+    use serde_json::json;
+    use url::Url;
+
+    use crate::api::{
+        self, v1, v2, ApiError, ApiVersion, AsyncClient, AsyncQuery, Client, Query, RestClient,
+    };
+    use crate::cassette::{CassetteClient, CassetteError};
+    use crate::mock::{Expectation, MockClient, MockClientError};
+    use crate::types::v1::{PostsUpdate, UserApiToken};
+    use crate::types::v2::{AuthToken, TagList};
+
+    const TOKEN: &str = "user:0123456789ABCDEF";
+
+    // A mock client which adds the token to its URLs like the real clients.
+    struct TokenClient(MockClient);
+
+    impl RestClient for TokenClient {
+        type Error = MockClientError;
+
+        fn rest_endpoint(
+            &self,
+            version: ApiVersion,
+            endpoint: &str,
+        ) -> Result<Url, ApiError<Self::Error>> {
+            let mut url = self.0.rest_endpoint(version, endpoint)?;
+            url.query_pairs_mut().append_pair("auth_token", TOKEN);
+            Ok(url)
+        }
+    }
+
+    impl Client for TokenClient {
+        fn rest(
+            &self,
+            request: RequestBuilder,
+            body: Vec<u8>,
+        ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+            self.0.rest(request, body)
+        }
+    }
+
+    #[async_trait]
+    impl AsyncClient for TokenClient {
+        async fn rest_async(
+            &self,
+            request: RequestBuilder,
+            body: Vec<u8>,
+        ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+            self.0.rest_async(request, body).await
+        }
+    }
+
+    fn cassette_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("pinboard-rs-{}", std::process::id()))
+            .join(format!("{}.json", name))
+    }
+
+    fn replay_client(path: &PathBuf) -> CassetteClient<TokenClient> {
+        CassetteClient::replay(TokenClient(MockClient::ordered()), path).unwrap()
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let path = cassette_path("record_and_replay");
+        let mock = MockClient::ordered();
+        mock.expect(
+            Expectation::get("v1/posts/update")
+                .respond_json(&json!({"update_time": "2024-01-01T00:00:00Z"})),
+        )
+        .expect(
+            Expectation::get("v1/user/api_token")
+                .respond_json(&json!({"result": "0123456789ABCDEF"})),
+        );
+
+        let client = CassetteClient::record(TokenClient(mock), &path).unwrap();
+        let update = v1::posts::Update::builder().build().unwrap();
+        let recorded: PostsUpdate = update.query(&client).unwrap();
+        let token = v1::user::ApiToken::builder().build().unwrap();
+        let _: UserApiToken = token.query(&client).unwrap();
+        drop(client);
+
+        let data = fs::read_to_string(&path).unwrap();
+        assert!(!data.contains("0123456789ABCDEF"));
+        assert!(data.contains("REDACTED"));
+
+        let client = replay_client(&path);
+        let replayed: PostsUpdate = update.query(&client).unwrap();
+        assert_eq!(recorded, replayed);
+        let secret: UserApiToken = token.query(&client).unwrap();
+        assert_eq!(secret.token, "REDACTED");
+    }
+
+    #[test]
+    fn record_v2_auth() {
+        let path = cassette_path("record_v2_auth");
+        let mock = MockClient::ordered();
+        mock.expect(
+            Expectation::post("v2/auth")
+                .body(r#"{"username":"user","password":"hunter2"}"#)
+                .respond_json(&json!({"token": "user:FEDCBA9876543210"})),
+        );
+
+        let client = CassetteClient::record(mock, &path).unwrap();
+        let auth = v2::general::Auth::builder()
+            .username("user")
+            .password("hunter2")
+            .build()
+            .unwrap();
+        let recorded: AuthToken = auth.query(&client).unwrap();
+        assert_eq!(recorded.token, "user:FEDCBA9876543210");
+        drop(client);
+
+        let data = fs::read_to_string(&path).unwrap();
+        assert!(!data.contains("hunter2"));
+        assert!(!data.contains("FEDCBA9876543210"));
+
+        let client = CassetteClient::replay(MockClient::ordered(), &path).unwrap();
+        let replayed: AuthToken = auth.query(&client).unwrap();
+        assert_eq!(replayed.token, "REDACTED");
+    }
+
+    #[test]
+    fn record_with_token() {
+        let path = cassette_path("record_with_token");
+        let mock = MockClient::ordered();
+        mock.expect(
+            Expectation::get("v1/user/api_token")
+                .respond_json(&json!({"result": "0123456789ABCDEF"})),
+        );
+
+        let client = CassetteClient::record(mock, &path)
+            .unwrap()
+            .with_token(TOKEN);
+        let token = v1::user::ApiToken::builder().build().unwrap();
+        let _: UserApiToken = token.query(&client).unwrap();
+        drop(client);
+
+        let data = fs::read_to_string(&path).unwrap();
+        assert!(!data.contains("0123456789ABCDEF"));
+    }
+
+    #[test]
+    fn record_binary_body() {
+        let path = cassette_path("record_binary_body");
+        let body = b"\xff\xfe0123456789ABCDEF\x00".to_vec();
+        let mock = MockClient::ordered();
+        mock.expect(Expectation::get("v1/posts/update").respond(body));
+
+        let client = CassetteClient::record(TokenClient(mock), &path).unwrap();
+        let update = || v1::posts::Update::builder().build().unwrap();
+        api::raw(update()).query(&client).unwrap();
+        drop(client);
+
+        let data = fs::read_to_string(&path).unwrap();
+        assert!(data.contains("base64"));
+
+        let client = replay_client(&path);
+        let replayed = api::raw(update()).query(&client).unwrap();
+        assert_eq!(replayed, b"\xff\xfeREDACTED\x00");
+    }
+
+    #[test]
+    fn replay_matches_query() {
+        let path = cassette_path("replay_matches_query");
+        let mock = MockClient::ordered();
+        mock.expect(Expectation::get("v1/posts/all").query("results", "5"));
+
+        let client = CassetteClient::record(TokenClient(mock), &path).unwrap();
+        let all = || v1::posts::All::builder().results(5).build().unwrap();
+        api::ignore(all()).query(&client).unwrap();
+        drop(client);
+
+        let client = replay_client(&path);
+        api::ignore(all()).query(&client).unwrap();
+        // Interactions are repeated once exhausted.
+        api::ignore(all()).query(&client).unwrap();
+
+        let all = v1::posts::All::builder().results(6).build().unwrap();
+        let err = api::ignore(all).query(&client).unwrap_err();
+        if let ApiError::Client {
+            source: CassetteError::NoInteraction { url, .. },
+        } = err
+        {
+            assert_eq!(url, "/v1/posts/all?results=6");
+        } else {
+            panic!("unexpected error: {}", err);
+        }
+    }
+
+    #[test]
+    fn replay_status() {
+        let path = cassette_path("replay_status");
+        let mock = MockClient::ordered();
+        mock.expect(
+            Expectation::get("v1/tags/get")
+                .status(StatusCode::NOT_FOUND)
+                .respond_json(&json!({"error_message": "nope"})),
+        );
+
+        let client = CassetteClient::record(TokenClient(mock), &path).unwrap();
+        let get = || v1::tags::Get::builder().build().unwrap();
+        assert!(api::ignore(get()).query(&client).is_err());
+        drop(client);
+
+        let client = replay_client(&path);
+        let err = api::ignore(get()).query(&client).unwrap_err();
+        assert!(matches!(err, ApiError::Pinboard { .. }));
+    }
+
+    #[tokio::test]
+    async fn record_and_replay_async() {
+        let path = cassette_path("record_and_replay_async");
+        let mock = MockClient::ordered();
+        mock.expect(Expectation::get("v2/tags").respond_json(&json!({"count": 0, "tags": []})));
+
+        let client = CassetteClient::record(TokenClient(mock), &path).unwrap();
+        let list = v2::tags::List::builder().build().unwrap();
+        let _: TagList = list.query_async(&client).await.unwrap();
+        drop(client);
+
+        let client = replay_client(&path);
+        let tags: TagList = list.query_async(&client).await.unwrap();
+        assert_eq!(tags.count, 0);
+    }
+}
@@ -0,0 +1,163 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt::{self, Debug};
+
+use crate::api;
+use crate::auth::{Auth, AuthError};
+use crate::base::{base_url, endpoint_url, PinboardResult};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::TryFutureExt;
+use http::Response as HttpResponse;
+use hyper::client::HttpConnector;
+use hyper::{Body, Client};
+use hyper_tls::HttpsConnector;
+use thiserror::Error;
+use url::Url;
+
+/// A representation of an asynchronous Pinboard API for a single user, using `hyper`
+///
+#[derive(Clone)]
+pub struct HyperPinboard {
+    /// The client to use for API calls
+    client: Client<HttpsConnector<HttpConnector>>,
+    /// The base URL for API calls
+    url: Url,
+    /// The authorization for communication with Pinboard
+    auth: Auth,
+}
+
+impl Debug for HyperPinboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HyperPinboard")
+            .field("url", &self.url)
+            .finish()
+    }
+}
+
+impl HyperPinboard {
+    /// Create a new async Pinboard API representation
+    ///
+    /// The `token` is the personal access token available at <https://pinboard.in>
+    pub async fn new<H, T>(host: H, token: T) -> PinboardResult<Self>
+    where
+        H: AsRef<str>,
+        T: Into<String>,
+    {
+        Self::new_impl("https", host.as_ref(), Auth::Token(token.into())).await
+    }
+
+    /// Create a new async Pinboard API representation which communicates over HTTP
+    ///
+    /// This is only meant for local servers, such as the one provided by the
+    /// `testing` feature. The token is sent in the clear.
+    pub async fn new_insecure<H, T>(host: H, token: T) -> PinboardResult<Self>
+    where
+        H: AsRef<str>,
+        T: Into<String>,
+    {
+        Self::new_impl("http", host.as_ref(), Auth::Token(token.into())).await
+    }
+
+    /// Internal method to create a new client
+    async fn new_impl(protocol: &str, host: &str, auth: Auth) -> PinboardResult<Self> {
+        let url = base_url(protocol, host)?;
+        let client = Client::builder().build(HttpsConnector::new());
+        let api = HyperPinboard { client, url, auth };
+
+        Ok(api)
+    }
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum HyperError {
+    #[error("error setting auth header: {}", source)]
+    AuthError {
+        #[from]
+        source: AuthError,
+    },
+    #[error("communication with pinboard: {}", source)]
+    Communication {
+        #[from]
+        source: hyper::Error,
+    },
+    #[error("`http` error: {}", source)]
+    Http {
+        #[from]
+        source: http::Error,
+    },
+}
+
+#[async_trait]
+impl api::AsyncClient for HyperPinboard {
+    /// Perform async hyper query
+    async fn rest_async(
+        &self,
+        mut request: http::request::Builder,
+        body: Vec<u8>,
+    ) -> Result<HttpResponse<Bytes>, api::ApiError<Self::Error>> {
+        let call = || async {
+            self.auth.set_header(request.headers_mut().unwrap())?;
+            let http_request = request.body(Body::from(body))?;
+            let rsp = self.client.request(http_request).await?;
+
+            let (parts, body) = rsp.into_parts();
+            let body = hyper::body::to_bytes(body).await?;
+            Ok(HttpResponse::from_parts(parts, body))
+        };
+        call().map_err(api::ApiError::client).await
+    }
+}
+
+impl api::RestClient for HyperPinboard {
+    type Error = HyperError;
+
+    fn rest_endpoint(
+        &self,
+        version: api::ApiVersion,
+        endpoint: &str,
+    ) -> Result<Url, api::ApiError<Self::Error>> {
+        Ok(endpoint_url(&self.url, &self.auth, version, endpoint)?)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use http::StatusCode;
+
+    use crate::api::{self, v1, AsyncQuery};
+    use crate::testing::FakePinboard;
+    use crate::types::v1::PostsUpdate;
+    use crate::HyperPinboard;
+
+    const TOKEN: &str = "user:0123456789ABCDEF";
+
+    #[tokio::test]
+    async fn query() {
+        let server = FakePinboard::start(TOKEN).unwrap();
+        let client = HyperPinboard::new_insecure(server.host(), TOKEN)
+            .await
+            .unwrap();
+
+        let update = v1::posts::Update::builder().build().unwrap();
+        let _: PostsUpdate = update.query_async(&client).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn error_status() {
+        let server = FakePinboard::start(TOKEN).unwrap();
+        let client = HyperPinboard::new_insecure(server.host(), "user:WRONG")
+            .await
+            .unwrap();
+
+        let update = v1::posts::Update::builder().build().unwrap();
+        let err = api::ignore(update).query_async(&client).await.unwrap_err();
+        assert!(err.is_auth_failure());
+        assert_eq!(err.status(), Some(StatusCode::UNAUTHORIZED));
+    }
+}
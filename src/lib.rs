@@ -8,17 +8,61 @@
 
 #[allow(clippy::needless_doctest_main)]
 #[doc = include_str!("../README.md")]
-#[cfg(feature = "async")]
+#[cfg(all(feature = "async", feature = "reqwest"))]
 mod async_pinboard;
+// Only the HTTP backends authenticate requests.
+#[cfg_attr(
+    not(any(
+        feature = "hyper",
+        feature = "reqwest",
+        feature = "tower",
+        feature = "ureq"
+    )),
+    allow(dead_code)
+)]
 mod auth;
+#[cfg_attr(
+    not(any(
+        feature = "hyper",
+        feature = "reqwest",
+        feature = "tower",
+        feature = "ureq"
+    )),
+    allow(dead_code)
+)]
+mod base;
+#[cfg(feature = "hyper")]
+mod hyper_pinboard;
+#[cfg(feature = "reqwest")]
 mod pinboard;
+#[cfg(feature = "ureq")]
+mod ureq_pinboard;
 
 pub mod api;
+pub mod cache;
+#[cfg(any(test, feature = "cassette"))]
+pub mod cassette;
+pub mod middleware;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+#[cfg(feature = "queue")]
+pub mod queue;
+#[cfg(feature = "tower")]
+pub mod service;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod types;
+mod wrapper;
 
-#[cfg(feature = "async")]
+#[cfg(all(feature = "async", feature = "reqwest"))]
 pub use crate::async_pinboard::AsyncPinboard;
-pub use crate::pinboard::{Pinboard, PinboardError};
+pub use crate::base::PinboardError;
+#[cfg(feature = "hyper")]
+pub use crate::hyper_pinboard::{HyperError, HyperPinboard};
+#[cfg(feature = "reqwest")]
+pub use crate::pinboard::Pinboard;
+#[cfg(feature = "ureq")]
+pub use crate::ureq_pinboard::{UreqError, UreqPinboard};
 
 #[cfg(test)]
 mod test;
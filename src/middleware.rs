@@ -0,0 +1,690 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hooks around the requests made by a client
+//!
+//! A [`Middleware`] sees each request before it is sent and each
+//! response before it is returned. [`MiddlewareClient`] runs a stack of
+//! them around any [`Client`] or [`AsyncClient`]: requests pass through
+//! the middleware in the order they were added and responses in the
+//! reverse order.
+//!
+//! Middleware which needs more than a look at each request, such as
+//! retrying failed requests, overrides [`Middleware::handle`] and calls
+//! the rest of the stack through [`Next::run`] as often as it needs. It
+//! can report its retries on the query's span with
+//! [`api::record_retries`](crate::api::record_retries).
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "reqwest")]
+//! # fn main() {
+//! use bytes::Bytes;
+//! use http::{HeaderValue, Request, Response};
+//! use pinboard_rs::middleware::{BoxError, Middleware, MiddlewareClient, RequestHead};
+//! use pinboard_rs::Pinboard;
+//!
+//! struct UserAgent;
+//!
+//! impl Middleware for UserAgent {
+//!     fn before_request(
+//!         &self,
+//!         request: &mut Request<Vec<u8>>,
+//!     ) -> Result<Option<Response<Bytes>>, BoxError> {
+//!         let agent = HeaderValue::from_static("my-sync-job/1.0");
+//!         request.headers_mut().insert(http::header::USER_AGENT, agent);
+//!         Ok(None)
+//!     }
+//! }
+//!
+//! struct Log;
+//!
+//! impl Middleware for Log {
+//!     fn after_response(
+//!         &self,
+//!         request: &RequestHead,
+//!         response: &mut Response<Bytes>,
+//!     ) -> Result<(), BoxError> {
+//!         println!("{} {} -> {}", request.method, request.uri.path(), response.status());
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let pb = Pinboard::new("api.pinboard.in", "<TOKEN>").unwrap();
+//! let client = MiddlewareClient::new(pb).with(UserAgent).with(Log);
+//! # }
+//! # #[cfg(not(feature = "reqwest"))]
+//! # fn main() {}
+//! ```
+
+use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::request::Builder as RequestBuilder;
+use http::{HeaderMap, Method, Request, Response, Uri};
+use thiserror::Error;
+use url::Url;
+
+use crate::api::{ApiError, ApiVersion, AsyncClient, Client, RestClient};
+
+/// A boxed error returned by a middleware
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+/// The method, URI and headers of a request that was sent
+#[derive(Debug, Clone)]
+pub struct RequestHead {
+    /// The method of the request
+    pub method: Method,
+    /// The URI of the request
+    pub uri: Uri,
+    /// The headers of the request
+    pub headers: HeaderMap,
+}
+
+impl RequestHead {
+    fn new<B>(request: &Request<B>) -> Self {
+        RequestHead {
+            method: request.method().clone(),
+            uri: request.uri().clone(),
+            headers: request.headers().clone(),
+        }
+    }
+}
+
+/// A hook around the requests made by a client
+///
+/// Most middleware only needs the `before_request`, `after_response` and
+/// `on_error` hooks. Middleware which needs to send a request more than
+/// once, e.g. to retry it, overrides [`Middleware::handle`] and
+/// [`Middleware::handle_async`] instead, which receive the rest of the
+/// stack as a [`Next`] or [`AsyncNext`].
+///
+/// Every method has a default which does nothing beyond running the
+/// other hooks.
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Inspect or modify a request before it is sent.
+    ///
+    /// Returning a response answers the request without sending it;
+    /// middleware added later is skipped. Returning an error fails the
+    /// request.
+    fn before_request(
+        &self,
+        request: &mut Request<Vec<u8>>,
+    ) -> Result<Option<Response<Bytes>>, BoxError> {
+        let _ = request;
+        Ok(None)
+    }
+
+    /// Inspect or modify a response before it is returned.
+    fn after_response(
+        &self,
+        request: &RequestHead,
+        response: &mut Response<Bytes>,
+    ) -> Result<(), BoxError> {
+        let _ = (request, response);
+        Ok(())
+    }
+
+    /// Observe a request which failed without a response.
+    fn on_error(&self, request: &RequestHead, error: &(dyn Error + 'static)) {
+        let _ = (request, error);
+    }
+
+    /// Send a blocking request through the rest of the stack.
+    ///
+    /// The default runs `before_request`, sends the request with `next`
+    /// unless it was answered, then runs `after_response` or `on_error`.
+    /// `next` may be run any number of times.
+    fn handle(
+        &self,
+        request: &mut Request<Vec<u8>>,
+        next: Next<'_>,
+    ) -> Result<Response<Bytes>, BoxError> {
+        if let Some(rsp) = self.before_request(request)? {
+            return Ok(rsp);
+        }
+        let result = next.run(request);
+        finish(self, request, result)
+    }
+
+    /// Send an asynchronous request through the rest of the stack.
+    ///
+    /// The default behaves like [`Middleware::handle`].
+    async fn handle_async(
+        &self,
+        request: &mut Request<Vec<u8>>,
+        next: AsyncNext<'_>,
+    ) -> Result<Response<Bytes>, BoxError> {
+        if let Some(rsp) = self.before_request(request)? {
+            return Ok(rsp);
+        }
+        let result = next.run(request).await;
+        finish(self, request, result)
+    }
+}
+
+/// Run the response hooks of `middleware` on the result of a request.
+fn finish<M>(
+    middleware: &M,
+    request: &Request<Vec<u8>>,
+    result: Result<Response<Bytes>, BoxError>,
+) -> Result<Response<Bytes>, BoxError>
+where
+    M: Middleware + ?Sized,
+{
+    let head = RequestHead::new(request);
+    match result {
+        Ok(mut rsp) => {
+            middleware.after_response(&head, &mut rsp)?;
+            Ok(rsp)
+        },
+        Err(err) => {
+            middleware.on_error(&head, err.as_ref());
+            Err(err)
+        },
+    }
+}
+
+#[async_trait]
+impl<M> Middleware for Arc<M>
+where
+    M: Middleware + ?Sized,
+{
+    fn before_request(
+        &self,
+        request: &mut Request<Vec<u8>>,
+    ) -> Result<Option<Response<Bytes>>, BoxError> {
+        (**self).before_request(request)
+    }
+
+    fn after_response(
+        &self,
+        request: &RequestHead,
+        response: &mut Response<Bytes>,
+    ) -> Result<(), BoxError> {
+        (**self).after_response(request, response)
+    }
+
+    fn on_error(&self, request: &RequestHead, error: &(dyn Error + 'static)) {
+        (**self).on_error(request, error)
+    }
+
+    fn handle(
+        &self,
+        request: &mut Request<Vec<u8>>,
+        next: Next<'_>,
+    ) -> Result<Response<Bytes>, BoxError> {
+        (**self).handle(request, next)
+    }
+
+    async fn handle_async(
+        &self,
+        request: &mut Request<Vec<u8>>,
+        next: AsyncNext<'_>,
+    ) -> Result<Response<Bytes>, BoxError> {
+        (**self).handle_async(request, next).await
+    }
+}
+
+type SendFn<'a> = dyn Fn(&Request<Vec<u8>>) -> Result<Response<Bytes>, BoxError> + 'a;
+type SendFuture<'a> = Pin<Box<dyn Future<Output = Result<Response<Bytes>, BoxError>> + Send + 'a>>;
+type SendAsyncFn<'a> = dyn Fn(&Request<Vec<u8>>) -> SendFuture<'a> + Send + Sync + 'a;
+
+/// The middleware after the current one and the client, for blocking requests
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    middleware: &'a [Box<dyn Middleware>],
+    send: &'a SendFn<'a>,
+}
+
+impl<'a> Next<'a> {
+    /// Send a copy of `request` through the rest of the stack.
+    ///
+    /// Later middleware only changes the copy, so each run sends `request`
+    /// as it is now.
+    pub fn run(&self, request: &Request<Vec<u8>>) -> Result<Response<Bytes>, BoxError> {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => {
+                let next = Next {
+                    middleware: rest,
+                    send: self.send,
+                };
+                middleware.handle(&mut copy_request(request), next)
+            },
+            None => (self.send)(request),
+        }
+    }
+}
+
+/// The middleware after the current one and the client, for asynchronous requests
+#[derive(Clone, Copy)]
+pub struct AsyncNext<'a> {
+    middleware: &'a [Box<dyn Middleware>],
+    send: &'a SendAsyncFn<'a>,
+}
+
+impl<'a> AsyncNext<'a> {
+    /// Send a copy of `request` through the rest of the stack.
+    ///
+    /// See [`Next::run`].
+    pub async fn run(&self, request: &Request<Vec<u8>>) -> Result<Response<Bytes>, BoxError> {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => {
+                let next = AsyncNext {
+                    middleware: rest,
+                    send: self.send,
+                };
+                middleware
+                    .handle_async(&mut copy_request(request), next)
+                    .await
+            },
+            None => (self.send)(request).await,
+        }
+    }
+}
+
+/// Errors from a [`MiddlewareClient`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum MiddlewareError<E>
+where
+    E: Error + Send + Sync + 'static,
+{
+    /// The inner client failed
+    #[error("{}", source)]
+    Client {
+        /// The inner client error.
+        source: E,
+    },
+
+    /// The request could not be assembled
+    #[error("failed to build request: {}", source)]
+    Request {
+        /// The source of the error.
+        source: http::Error,
+    },
+
+    /// A middleware failed the request
+    #[error("middleware error: {}", source)]
+    Middleware {
+        /// The source of the error.
+        source: BoxError,
+    },
+}
+
+/// A client which runs middleware around the requests of another client
+pub struct MiddlewareClient<C> {
+    inner: C,
+    middleware: Vec<Box<dyn Middleware>>,
+}
+
+type MiddlewareApiError<C> = ApiError<MiddlewareError<<C as RestClient>::Error>>;
+
+impl<C> MiddlewareClient<C>
+where
+    C: RestClient,
+{
+    /// Wrap `inner` without any middleware.
+    pub fn new(inner: C) -> Self {
+        MiddlewareClient {
+            inner,
+            middleware: Vec::new(),
+        }
+    }
+
+    /// Add a middleware to the end of the stack.
+    pub fn with<M>(mut self, middleware: M) -> Self
+    where
+        M: Middleware + 'static,
+    {
+        self.middleware.push(Box::new(middleware));
+        self
+    }
+
+    /// The inner client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Box an error from the inner client to pass it through the stack.
+    fn box_error(err: ApiError<C::Error>) -> BoxError {
+        Box::new(err.map_client(|source| MiddlewareError::Client { source }))
+    }
+
+    /// Recover an error from the inner client, or wrap a middleware error.
+    fn unbox_error(err: BoxError) -> MiddlewareApiError<C> {
+        match err.downcast::<MiddlewareApiError<C>>() {
+            Ok(err) => *err,
+            Err(source) => ApiError::client(MiddlewareError::Middleware { source }),
+        }
+    }
+}
+
+/// Build a request from the builder and body clients take.
+fn into_request<C>(
+    request: RequestBuilder,
+    body: Vec<u8>,
+) -> Result<Request<Vec<u8>>, MiddlewareApiError<C>>
+where
+    C: RestClient,
+{
+    request
+        .body(body)
+        .map_err(|source| ApiError::client(MiddlewareError::Request { source }))
+}
+
+/// Copy a request, leaving the original to be sent again.
+fn copy_request(request: &Request<Vec<u8>>) -> Request<Vec<u8>> {
+    let mut copy = Request::new(request.body().clone());
+    *copy.method_mut() = request.method().clone();
+    *copy.uri_mut() = request.uri().clone();
+    *copy.version_mut() = request.version();
+    *copy.headers_mut() = request.headers().clone();
+    copy
+}
+
+/// Copy a request into the builder and body clients take.
+fn to_builder(request: &Request<Vec<u8>>) -> (RequestBuilder, Vec<u8>) {
+    let mut builder = Request::builder()
+        .method(request.method().clone())
+        .uri(request.uri().clone())
+        .version(request.version());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = request.headers().clone();
+    }
+    (builder, request.body().clone())
+}
+
+impl<C> RestClient for MiddlewareClient<C>
+where
+    C: RestClient,
+{
+    type Error = MiddlewareError<C::Error>;
+
+    fn rest_endpoint(
+        &self,
+        version: ApiVersion,
+        endpoint: &str,
+    ) -> Result<Url, ApiError<Self::Error>> {
+        self.inner
+            .rest_endpoint(version, endpoint)
+            .map_err(|err| err.map_client(|source| MiddlewareError::Client { source }))
+    }
+}
+
+impl<C> Client for MiddlewareClient<C>
+where
+    C: Client,
+{
+    fn rest(
+        &self,
+        request: RequestBuilder,
+        body: Vec<u8>,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let request = into_request::<C>(request, body)?;
+        let send = |request: &Request<Vec<u8>>| {
+            let (request, body) = to_builder(request);
+            self.inner.rest(request, body).map_err(Self::box_error)
+        };
+        let next = Next {
+            middleware: &self.middleware,
+            send: &send,
+        };
+        next.run(&request).map_err(Self::unbox_error)
+    }
+}
+
+#[async_trait]
+impl<C> AsyncClient for MiddlewareClient<C>
+where
+    C: AsyncClient + Sync,
+{
+    async fn rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Vec<u8>,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let request = into_request::<C>(request, body)?;
+        let inner = &self.inner;
+        let send = move |request: &Request<Vec<u8>>| -> SendFuture<'_> {
+            let (request, body) = to_builder(request);
+            Box::pin(async move {
+                inner
+                    .rest_async(request, body)
+                    .await
+                    .map_err(Self::box_error)
+            })
+        };
+        let next = AsyncNext {
+            middleware: &self.middleware,
+            send: &send,
+        };
+        next.run(&request).await.map_err(Self::unbox_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+    use std::sync::{Arc, Mutex};
+
+    use bytes::Bytes;
+    use http::{HeaderValue, Request, Response, StatusCode};
+    use serde_json::json;
+
+    use crate::api::{self, v1, v2, ApiError, AsyncQuery, Query};
+    use crate::middleware::{BoxError, Middleware, MiddlewareClient, MiddlewareError, RequestHead};
+    use crate::mock::{Expectation, MockClient};
+    use crate::test::middleware::Retry;
+    use crate::types::v2::TagList;
+
+    /// Records the hooks it sees under a name.
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Recorder {
+        fn new(name: &'static str, log: &Arc<Mutex<Vec<String>>>) -> Self {
+            Recorder {
+                name,
+                log: Arc::clone(log),
+            }
+        }
+
+        fn push(&self, event: String) {
+            self.log.lock().unwrap().push(event);
+        }
+    }
+
+    impl Middleware for Recorder {
+        fn before_request(
+            &self,
+            request: &mut Request<Vec<u8>>,
+        ) -> Result<Option<Response<Bytes>>, BoxError> {
+            let seen = request.headers().get("x-seen").cloned();
+            self.push(format!("{} before {:?}", self.name, seen));
+            request
+                .headers_mut()
+                .insert("x-seen", HeaderValue::from_static(self.name));
+            Ok(None)
+        }
+
+        fn after_response(
+            &self,
+            request: &RequestHead,
+            response: &mut Response<Bytes>,
+        ) -> Result<(), BoxError> {
+            self.push(format!(
+                "{} after {} {}",
+                self.name,
+                request.uri.path(),
+                response.status().as_u16(),
+            ));
+            Ok(())
+        }
+
+        fn on_error(&self, _: &RequestHead, error: &(dyn Error + 'static)) {
+            self.push(format!("{} error {}", self.name, error));
+        }
+    }
+
+    /// Answers every request with a canned status.
+    struct Fault(StatusCode);
+
+    impl Middleware for Fault {
+        fn before_request(
+            &self,
+            _: &mut Request<Vec<u8>>,
+        ) -> Result<Option<Response<Bytes>>, BoxError> {
+            let mut rsp = Response::new(Bytes::from_static(b"injected"));
+            *rsp.status_mut() = self.0;
+            Ok(Some(rsp))
+        }
+    }
+
+    /// Fails every request.
+    struct Deny;
+
+    impl Middleware for Deny {
+        fn before_request(
+            &self,
+            _: &mut Request<Vec<u8>>,
+        ) -> Result<Option<Response<Bytes>>, BoxError> {
+            Err("denied".into())
+        }
+    }
+
+    fn log() -> Arc<Mutex<Vec<String>>> {
+        Arc::new(Mutex::new(Vec::new()))
+    }
+
+    #[test]
+    fn order() {
+        let mock = MockClient::ordered();
+        mock.expect(Expectation::get("v1/tags/get").respond_json(&json!({})));
+        let log = log();
+        let client = MiddlewareClient::new(mock)
+            .with(Recorder::new("a", &log))
+            .with(Recorder::new("b", &log));
+
+        let get = v1::tags::Get::builder().build().unwrap();
+        api::ignore(get).query(&client).unwrap();
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "a before None",
+                "b before Some(\"a\")",
+                "b after /v1/tags/get 200",
+                "a after /v1/tags/get 200",
+            ],
+        );
+    }
+
+    #[test]
+    fn short_circuit() {
+        let log = log();
+        let client = MiddlewareClient::new(MockClient::ordered())
+            .with(Recorder::new("a", &log))
+            .with(Fault(StatusCode::SERVICE_UNAVAILABLE))
+            .with(Recorder::new("b", &log));
+
+        let get = v1::tags::Get::builder().build().unwrap();
+        let err = api::ignore(get).query(&client).unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["a before None", "a after /v1/tags/get 503"],
+        );
+    }
+
+    #[test]
+    fn middleware_error() {
+        let log = log();
+        let client = MiddlewareClient::new(MockClient::ordered())
+            .with(Recorder::new("a", &log))
+            .with(Deny);
+
+        let get = v1::tags::Get::builder().build().unwrap();
+        let err = api::ignore(get).query(&client).unwrap_err();
+        if let ApiError::Client {
+            source: MiddlewareError::Middleware { source },
+        } = err
+        {
+            assert_eq!(source.to_string(), "denied");
+        } else {
+            panic!("unexpected error: {}", err);
+        }
+    }
+
+    #[tokio::test]
+    async fn async_client() {
+        let mock = MockClient::ordered();
+        mock.expect(Expectation::get("v2/tags").respond_json(&json!({"count": 0, "tags": []})));
+        let log = log();
+        let client = MiddlewareClient::new(mock).with(Arc::new(Recorder::new("a", &log)));
+
+        let list = v2::tags::List::builder().build().unwrap();
+        let _: TagList = list.query_async(&client).await.unwrap();
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["a before None", "a after /v2/tags 200"],
+        );
+    }
+
+    #[test]
+    fn retry() {
+        let mock = MockClient::ordered();
+        mock.expect(Expectation::get("v1/tags/get").status(StatusCode::BAD_GATEWAY));
+        mock.expect(Expectation::get("v1/tags/get").respond_json(&json!({})));
+        let log = log();
+        let client = MiddlewareClient::new(mock)
+            .with(Recorder::new("a", &log))
+            .with(Retry(2))
+            .with(Recorder::new("b", &log));
+
+        let get = v1::tags::Get::builder().build().unwrap();
+        api::ignore(get).query(&client).unwrap();
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "a before None",
+                "b before Some(\"a\")",
+                "b after /v1/tags/get 502",
+                "b before Some(\"a\")",
+                "b after /v1/tags/get 200",
+                "a after /v1/tags/get 200",
+            ],
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_async() {
+        let mock = MockClient::ordered();
+        mock.expect(Expectation::get("v2/tags").status(StatusCode::BAD_GATEWAY));
+        mock.expect(Expectation::get("v2/tags").respond_json(&json!({"count": 0, "tags": []})));
+        let log = log();
+        let client = MiddlewareClient::new(mock)
+            .with(Retry(2))
+            .with(Recorder::new("a", &log));
+
+        let list = v2::tags::List::builder().build().unwrap();
+        let _: TagList = list.query_async(&client).await.unwrap();
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "a before None",
+                "a after /v2/tags 502",
+                "a before None",
+                "a after /v2/tags 200",
+            ],
+        );
+    }
+}
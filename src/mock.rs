@@ -0,0 +1,426 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A scripted client for tests
+//!
+//! [`MockClient`] serves canned responses for a script of expected
+//! requests, so flows spanning several endpoints can be tested without
+//! a network. It is available with the `mock` feature.
+//!
+//! ```rust
+//! # fn main() {
+//! use pinboard_rs::api::{self, v1::posts, Query};
+//! use pinboard_rs::mock::{Expectation, MockClient};
+//! use pinboard_rs::types::v1::PostsUpdate;
+//! use serde_json::json;
+//!
+//! let client = MockClient::ordered();
+//! client
+//!     .expect(
+//!         Expectation::get("v1/posts/update")
+//!             .respond_json(&json!({"update_time": "2024-01-01T00:00:00Z"})),
+//!     )
+//!     .expect(Expectation::get("v1/posts/all").query("results", "10"));
+//!
+//! let _: PostsUpdate = posts::Update::builder().build().unwrap().query(&client).unwrap();
+//! let all = posts::All::builder().results(10).build().unwrap();
+//! api::ignore(all).query(&client).unwrap();
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::request::Builder as RequestBuilder;
+use http::{HeaderMap, HeaderName, HeaderValue, Method, Response, StatusCode};
+use serde::Serialize;
+use thiserror::Error;
+use url::Url;
+
+use crate::api::{ApiError, ApiVersion, AsyncClient, Client, RestClient};
+use crate::wrapper;
+
+const MOCK_BASE: &str = "https://api.pinboard.mock/";
+
+/// A request expected by a [`MockClient`] and the response to give it.
+#[derive(Debug, Clone)]
+pub struct Expectation {
+    method: Method,
+    path: String,
+    query: Vec<(String, String)>,
+    body: Option<Vec<u8>>,
+    status: StatusCode,
+    headers: HeaderMap,
+    response: Vec<u8>,
+}
+
+impl Expectation {
+    /// Expect a request with `method` to `path`.
+    ///
+    /// The path includes the version prefix, e.g. `v1/posts/all` or
+    /// `v2/tags`. The default response is an empty `200 OK`.
+    pub fn new<P>(method: Method, path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        Expectation {
+            method,
+            path: path.into(),
+            query: Vec::new(),
+            body: None,
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            response: Vec::new(),
+        }
+    }
+
+    /// Expect a `GET` request to `path`.
+    pub fn get<P>(path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        Self::new(Method::GET, path)
+    }
+
+    /// Expect a `POST` request to `path`.
+    pub fn post<P>(path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        Self::new(Method::POST, path)
+    }
+
+    /// Expect a `DELETE` request to `path`.
+    pub fn delete<P>(path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        Self::new(Method::DELETE, path)
+    }
+
+    /// Require a query parameter.
+    ///
+    /// Parameters which are not required are ignored when matching.
+    pub fn query<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.query.push((key.into(), value.into()));
+        self
+    }
+
+    /// Require the request body to be exactly `body`.
+    pub fn body<B>(mut self, body: B) -> Self
+    where
+        B: Into<Vec<u8>>,
+    {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Respond with `status`.
+    pub fn status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Respond with a header.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name or value is not a valid header.
+    pub fn header<K, V>(mut self, name: K, value: V) -> Self
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let name = HeaderName::from_bytes(name.as_ref().as_bytes()).expect("invalid header name");
+        let value = HeaderValue::from_str(value.as_ref()).expect("invalid header value");
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Respond with `data` as the body.
+    pub fn respond<D>(mut self, data: D) -> Self
+    where
+        D: Into<Vec<u8>>,
+    {
+        self.response = data.into();
+        self
+    }
+
+    /// Respond with `data` serialized as JSON.
+    pub fn respond_json<T>(self, data: &T) -> Self
+    where
+        T: Serialize,
+    {
+        let data = serde_json::to_vec(data).expect("failed to serialize mock response");
+        self.respond(data)
+    }
+
+    fn matches(&self, method: &Method, url: &Url, body: &[u8]) -> bool {
+        *method == self.method
+            && url.path() == format!("/{}", self.path)
+            && self.query.iter().all(|(key, value)| {
+                url.query_pairs()
+                    .any(|(k, v)| k == key.as_str() && v == value.as_str())
+            })
+            && self.body.as_ref().map_or(true, |expected| expected == body)
+    }
+
+    fn response(&self) -> Response<Bytes> {
+        let mut rsp = Response::builder()
+            .status(self.status)
+            .body(self.response.clone().into())
+            .unwrap();
+        *rsp.headers_mut() = self.headers.clone();
+        rsp
+    }
+}
+
+impl fmt::Display for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} /{}", self.method, self.path)?;
+        for (i, (key, value)) in self.query.iter().enumerate() {
+            write!(f, "{}{}={}", if i == 0 { "?" } else { "&" }, key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Errors from a [`MockClient`].
+///
+/// The mock client never fails; mismatched requests panic instead.
+#[derive(Debug, Error)]
+#[error("mock client error")]
+pub enum MockClientError {}
+
+/// A client serving canned responses for a script of expected requests.
+///
+/// An ordered client requires requests to arrive in the order they were
+/// expected; an unordered client serves each request from the first
+/// remaining expectation it matches. Each expectation is consumed by
+/// one request. A request matching no expectation panics, as does
+/// dropping the client while expectations remain.
+pub struct MockClient {
+    ordered: bool,
+    expectations: Mutex<VecDeque<Expectation>>,
+}
+
+impl MockClient {
+    /// A client expecting requests in order.
+    pub fn ordered() -> Self {
+        Self::new(true)
+    }
+
+    /// A client expecting requests in any order.
+    pub fn unordered() -> Self {
+        Self::new(false)
+    }
+
+    fn new(ordered: bool) -> Self {
+        MockClient {
+            ordered,
+            expectations: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Add an expectation to the script.
+    pub fn expect(&self, expectation: Expectation) -> &Self {
+        self.lock().push_back(expectation);
+        self
+    }
+
+    /// The number of expectations not yet consumed.
+    pub fn remaining(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<Expectation>> {
+        // A panicking assertion poisons the lock; the script is still usable.
+        wrapper::lock(&self.expectations)
+    }
+
+    fn serve(&self, request: RequestBuilder, body: Vec<u8>) -> Response<Bytes> {
+        let method = request.method_ref().unwrap().clone();
+        let url = Url::parse(&request.uri_ref().unwrap().to_string()).unwrap();
+
+        let mut expectations = self.lock();
+        let index = if self.ordered {
+            match expectations.front() {
+                Some(next) if next.matches(&method, &url, &body) => Some(0),
+                Some(next) => {
+                    let next = next.to_string();
+                    drop(expectations);
+                    panic!(
+                        "unexpected request `{} {}`; expected `{}`",
+                        method, url, next
+                    );
+                },
+                None => None,
+            }
+        } else {
+            expectations
+                .iter()
+                .position(|expected| expected.matches(&method, &url, &body))
+        };
+
+        match index.and_then(|index| expectations.remove(index)) {
+            Some(expected) => expected.response(),
+            None => {
+                drop(expectations);
+                panic!("unexpected request `{} {}`", method, url)
+            },
+        }
+    }
+}
+
+impl fmt::Debug for MockClient {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MockClient")
+            .field("ordered", &self.ordered)
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+
+impl Drop for MockClient {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+        let remaining = self.lock();
+        if !remaining.is_empty() {
+            let list: Vec<_> = remaining.iter().map(ToString::to_string).collect();
+            panic!("unconsumed mock expectations: {}", list.join(", "));
+        }
+    }
+}
+
+impl RestClient for MockClient {
+    type Error = MockClientError;
+
+    fn rest_endpoint(
+        &self,
+        version: ApiVersion,
+        endpoint: &str,
+    ) -> Result<Url, ApiError<Self::Error>> {
+        let base = Url::parse(MOCK_BASE)?;
+        match version {
+            ApiVersion::V2 => Ok(base.join("v2/")?.join(endpoint)?),
+            _ => Ok(base.join(endpoint)?),
+        }
+    }
+}
+
+impl Client for MockClient {
+    fn rest(
+        &self,
+        request: RequestBuilder,
+        body: Vec<u8>,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        Ok(self.serve(request, body))
+    }
+}
+
+#[async_trait]
+impl AsyncClient for MockClient {
+    async fn rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Vec<u8>,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        Ok(self.serve(request, body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+    use serde_json::json;
+
+    use crate::api::{self, v1, v2, ApiError, AsyncQuery, Query};
+    use crate::mock::{Expectation, MockClient};
+    use crate::types::v2::TagList;
+
+    #[test]
+    fn ordered() {
+        let client = MockClient::ordered();
+        client
+            .expect(Expectation::get("v1/posts/update"))
+            .expect(Expectation::get("v1/posts/all").query("results", "5"));
+
+        let update = v1::posts::Update::builder().build().unwrap();
+        api::ignore(update).query(&client).unwrap();
+        assert_eq!(client.remaining(), 1);
+        let all = v1::posts::All::builder().results(5).build().unwrap();
+        api::ignore(all).query(&client).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "expected `GET /v1/posts/update`")]
+    fn ordered_out_of_order() {
+        let client = MockClient::ordered();
+        client
+            .expect(Expectation::get("v1/posts/update"))
+            .expect(Expectation::get("v1/posts/all"));
+
+        let all = v1::posts::All::builder().build().unwrap();
+        let _ = api::ignore(all).query(&client);
+    }
+
+    #[test]
+    fn unordered() {
+        let client = MockClient::unordered();
+        client
+            .expect(Expectation::get("v1/posts/update"))
+            .expect(Expectation::post("v2/tags/rename").body("old=a&new=b"));
+
+        let rename = v2::tags::Rename::builder()
+            .old("a")
+            .new("b")
+            .build()
+            .unwrap();
+        api::ignore(rename).query(&client).unwrap();
+        let update = v1::posts::Update::builder().build().unwrap();
+        api::ignore(update).query(&client).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "unconsumed mock expectations: GET /v1/tags/get")]
+    fn unconsumed() {
+        let client = MockClient::unordered();
+        client.expect(Expectation::get("v1/tags/get"));
+    }
+
+    #[test]
+    fn status() {
+        let client = MockClient::ordered();
+        client.expect(
+            Expectation::get("v1/tags/get")
+                .status(StatusCode::NOT_FOUND)
+                .respond_json(&json!({"error_message": "nope"})),
+        );
+
+        let get = v1::tags::Get::builder().build().unwrap();
+        let err = api::ignore(get).query(&client).unwrap_err();
+        assert!(matches!(err, ApiError::Pinboard { .. }));
+    }
+
+    #[tokio::test]
+    async fn async_client() {
+        let client = MockClient::ordered();
+        client.expect(Expectation::get("v2/tags").respond_json(&json!({"count": 0, "tags": []})));
+
+        let list = v2::tags::List::builder().build().unwrap();
+        let tags: TagList = list.query_async(&client).await.unwrap();
+        assert_eq!(tags.count, 0);
+    }
+}
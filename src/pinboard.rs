@@ -9,30 +9,13 @@ use std::fmt::{self, Debug};
 
 use crate::api;
 use crate::auth::{Auth, AuthError};
+use crate::base::{base_url, endpoint_url, PinboardResult};
 use bytes::Bytes;
 use http::Response as HttpResponse;
 use reqwest::blocking::Client;
 use thiserror::Error;
 use url::Url;
 
-#[derive(Debug, Error)]
-#[non_exhaustive]
-pub enum PinboardError {
-    #[error("failed to parse url: {}", source)]
-    UrlParse {
-        #[from]
-        source: url::ParseError,
-    },
-
-    #[error("error setting auth header: {}", source)]
-    AuthError {
-        #[from]
-        source: AuthError,
-    },
-}
-
-pub type PinboardResult<T> = Result<T, PinboardError>;
-
 /// A pinboard API for a single user
 ///
 #[derive(Clone)]
@@ -60,12 +43,24 @@ impl Pinboard {
         H: AsRef<str>,
         T: Into<String>,
     {
-        Self::new_impl(host.as_ref(), Auth::Token(token.into()))
+        Self::new_impl("https", host.as_ref(), Auth::Token(token.into()))
+    }
+
+    /// Create a new Pinboard API representation which communicates over HTTP
+    ///
+    /// This is only meant for local servers, such as the one provided by the
+    /// `testing` feature. The token is sent in the clear.
+    pub fn new_insecure<H, T>(host: H, token: T) -> PinboardResult<Self>
+    where
+        H: AsRef<str>,
+        T: Into<String>,
+    {
+        Self::new_impl("http", host.as_ref(), Auth::Token(token.into()))
     }
 
     /// Internal method to create a new client
-    fn new_impl(host: &str, auth: Auth) -> PinboardResult<Self> {
-        let url = Url::parse(&format!("https://{}/", host))?;
+    fn new_impl(protocol: &str, host: &str, auth: Auth) -> PinboardResult<Self> {
+        let url = base_url(protocol, host)?;
         let client = Client::new();
         let api = Pinboard { client, url, auth };
 
@@ -96,11 +91,12 @@ pub enum RestError {
 impl api::RestClient for Pinboard {
     type Error = RestError;
 
-    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, api::ApiError<Self::Error>> {
-        let mut url = self.url.join(endpoint)?;
-        self.auth.add_to_url(&mut url);
-        url.query_pairs_mut().append_pair("format", "json");
-        Ok(url)
+    fn rest_endpoint(
+        &self,
+        version: api::ApiVersion,
+        endpoint: &str,
+    ) -> Result<Url, api::ApiError<Self::Error>> {
+        Ok(endpoint_url(&self.url, &self.auth, version, endpoint)?)
     }
 }
 
@@ -0,0 +1,558 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A request queue shared by the tasks using one account
+//!
+//! Pinboard allows one call every 3 seconds per account, and fewer for
+//! some endpoints. [`QueueClient`] wraps an [`AsyncClient`] so that its
+//! clones, which share one queue, send one request at a time and wait out
+//! the limits between them. Waiting requests are sent by [`Priority`],
+//! then in the order they were made. A request whose future is dropped
+//! while waiting leaves the queue.
+//!
+//! ```rust,no_run
+//! # #[cfg(all(feature = "async", feature = "reqwest"))]
+//! # async fn example() {
+//! use pinboard_rs::api::{self, v1, AsyncQuery};
+//! use pinboard_rs::queue::{Priority, QueueClient};
+//! use pinboard_rs::types::v1::Posts;
+//! use pinboard_rs::AsyncPinboard;
+//!
+//! let pb = AsyncPinboard::new("api.pinboard.in", "<TOKEN>").await.unwrap();
+//! let backfill = QueueClient::new(pb).with_priority(Priority::Low);
+//! let lookups = backfill.clone().with_priority(Priority::High);
+//!
+//! tokio::spawn(async move {
+//!     let all = v1::posts::All::builder().build().unwrap();
+//!     let _: Posts = all.query_async(&backfill).await.unwrap();
+//! });
+//!
+//! // Sent before any other low priority requests still waiting.
+//! let get = v1::tags::Get::builder().build().unwrap();
+//! api::ignore(get).query_async(&lookups).await.unwrap();
+//! # }
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::future;
+use http::request::Builder as RequestBuilder;
+use http::Response;
+use tokio::sync::Notify;
+use tokio::time::{self, Instant};
+use url::Url;
+
+use crate::api::v1::posts;
+use crate::api::{self, ApiError, ApiVersion, AsyncClient, RestClient};
+use crate::wrapper;
+
+/// The priority of requests made through a [`QueueClient`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Priority {
+    /// Requests which can wait, such as bulk backfills
+    Low,
+    /// The default priority
+    #[default]
+    Normal,
+    /// Requests which should jump the queue, such as interactive lookups
+    High,
+}
+
+/// The position of a waiting request: highest priority, then oldest first
+type Ticket = (Reverse<Priority>, u64);
+
+/// A request waiting for its turn
+struct Waiting {
+    /// The path without the leading slash, e.g. `v1/posts/all`
+    path: String,
+    wake: Arc<Notify>,
+}
+
+struct QueueState {
+    next_ticket: u64,
+    waiting: BTreeMap<Ticket, Waiting>,
+    /// The request allowed to be sent, which has not picked up its turn yet
+    granted: Option<Ticket>,
+    /// Whether a request is being sent
+    busy: bool,
+    last_call: Option<Instant>,
+    last_calls: HashMap<String, Instant>,
+    min_interval: Duration,
+    intervals: HashMap<String, Duration>,
+}
+
+impl QueueState {
+    /// When a request to `path` respects the limits.
+    fn ready_at(&self, path: &str, now: Instant) -> Instant {
+        let after =
+            |last: Option<&Instant>, interval: Duration| last.map_or(now, |last| *last + interval);
+        let interval = self.intervals.get(path).copied().unwrap_or_default();
+        after(self.last_call.as_ref(), self.min_interval)
+            .max(after(self.last_calls.get(path), interval))
+    }
+
+    /// Let the first waiting request which respects the limits be sent.
+    fn dispatch(&mut self, now: Instant) {
+        if self.busy {
+            return;
+        }
+        let next = self
+            .waiting
+            .iter()
+            .find(|(_, waiting)| self.ready_at(&waiting.path, now) <= now)
+            .map(|(ticket, _)| *ticket);
+        if let Some(ticket) = next {
+            let waiting = self
+                .waiting
+                .remove(&ticket)
+                .expect("the ticket was just found");
+            self.busy = true;
+            self.granted = Some(ticket);
+            waiting.wake.notify_one();
+        }
+    }
+
+    /// Have every waiting request check the limits again.
+    fn wake_all(&self) {
+        for waiting in self.waiting.values() {
+            waiting.wake.notify_one();
+        }
+    }
+}
+
+struct Queue {
+    state: Mutex<QueueState>,
+}
+
+impl Queue {
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        wrapper::lock(&self.state)
+    }
+
+    /// Wait for the turn of a request to `path`.
+    async fn acquire(self: &Arc<Self>, priority: Priority, path: String) -> Turn {
+        let mut limit_wait = None;
+        let wake = Arc::new(Notify::new());
+        let ticket = {
+            let mut state = self.lock();
+            let ticket = (Reverse(priority), state.next_ticket);
+            state.next_ticket += 1;
+            let waiting = Waiting {
+                path: path.clone(),
+                wake: Arc::clone(&wake),
+            };
+            state.waiting.insert(ticket, waiting);
+            ticket
+        };
+        let mut place = Place {
+            queue: Arc::clone(self),
+            ticket: Some(ticket),
+        };
+
+        loop {
+            let deadline = {
+                let mut state = self.lock();
+                let now = Instant::now();
+                state.dispatch(now);
+                if state.granted == Some(ticket) {
+                    state.granted = None;
+                    place.ticket = None;
+                    break;
+                }
+                Some(state.ready_at(&path, now)).filter(|ready| *ready > now)
+            };
+
+            let notified = wake.notified();
+            match deadline {
+                // Check again once the limits allow this request.
+                Some(deadline) => {
+                    let start = Instant::now();
+                    let sleep = time::sleep_until(deadline);
+                    tokio::pin!(notified);
+                    tokio::pin!(sleep);
+                    future::select(notified, sleep).await;
+                    *limit_wait.get_or_insert(Duration::ZERO) += Instant::now() - start;
+                },
+                // Only waiting on another request or a higher priority.
+                None => notified.await,
+            }
+        }
+
+        if let Some(waited) = limit_wait {
+            api::record_limit_wait(&format!("/{}", path), waited);
+        }
+        Turn {
+            queue: Arc::clone(self),
+            path,
+        }
+    }
+}
+
+/// A place in the queue, given up if the request is dropped while waiting
+struct Place {
+    queue: Arc<Queue>,
+    ticket: Option<Ticket>,
+}
+
+impl Drop for Place {
+    fn drop(&mut self) {
+        let Some(ticket) = self.ticket else {
+            return;
+        };
+        let mut state = self.queue.lock();
+        state.waiting.remove(&ticket);
+        // The turn was granted, but never taken.
+        if state.granted == Some(ticket) {
+            state.granted = None;
+            state.busy = false;
+        }
+        state.dispatch(Instant::now());
+    }
+}
+
+/// The turn of a request being sent, passed on when dropped
+struct Turn {
+    queue: Arc<Queue>,
+    path: String,
+}
+
+impl Drop for Turn {
+    fn drop(&mut self) {
+        let mut state = self.queue.lock();
+        let now = Instant::now();
+        state.busy = false;
+        state.last_call = Some(now);
+        state.last_calls.insert(std::mem::take(&mut self.path), now);
+        state.wake_all();
+    }
+}
+
+/// An asynchronous client which queues requests to respect the API limits
+///
+/// Clones share the queue, so one client, cloned into every task using an
+/// account, sends that account's requests one at a time. Each clone sends
+/// its requests with its own [`Priority`].
+pub struct QueueClient<C> {
+    inner: Arc<C>,
+    queue: Arc<Queue>,
+    priority: Priority,
+}
+
+impl<C> Clone for QueueClient<C> {
+    fn clone(&self) -> Self {
+        QueueClient {
+            inner: Arc::clone(&self.inner),
+            queue: Arc::clone(&self.queue),
+            priority: self.priority,
+        }
+    }
+}
+
+impl<C> QueueClient<C> {
+    /// Queue the requests to `inner` with the API limits.
+    ///
+    /// Requests are 3 seconds apart, `v1/posts/recent` requests a minute
+    /// apart and `v1/posts/all` requests 5 minutes apart.
+    pub fn new(inner: C) -> Self {
+        let state = QueueState {
+            next_ticket: 0,
+            waiting: BTreeMap::new(),
+            granted: None,
+            busy: false,
+            last_call: None,
+            last_calls: HashMap::new(),
+            min_interval: wrapper::interval::<posts::Update>(),
+            intervals: wrapper::endpoint_intervals()
+                .into_iter()
+                .map(|(path, interval)| (path.into(), interval))
+                .collect(),
+        };
+
+        QueueClient {
+            inner: Arc::new(inner),
+            queue: Arc::new(Queue {
+                state: Mutex::new(state),
+            }),
+            priority: Priority::default(),
+        }
+    }
+
+    /// Send the requests of this client with `priority`.
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Leave at least `interval` between any two requests.
+    ///
+    /// This applies to every clone sharing the queue.
+    pub fn min_interval(self, interval: Duration) -> Self {
+        self.queue.lock().min_interval = interval;
+        self
+    }
+
+    /// Leave at least `interval` between two requests to `endpoint`.
+    ///
+    /// Endpoints are given by their path, e.g. `v1/posts/all` or `v2/tags`.
+    /// This applies to every clone sharing the queue.
+    pub fn interval<E>(self, endpoint: E, interval: Duration) -> Self
+    where
+        E: Into<String>,
+    {
+        self.queue
+            .lock()
+            .intervals
+            .insert(endpoint.into(), interval);
+        self
+    }
+
+    /// The priority of the requests of this client.
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    /// The number of requests waiting in the queue.
+    pub fn waiting(&self) -> usize {
+        self.queue.lock().waiting.len()
+    }
+
+    /// The inner client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+}
+
+impl<C> RestClient for QueueClient<C>
+where
+    C: RestClient,
+{
+    type Error = C::Error;
+
+    fn rest_endpoint(
+        &self,
+        version: ApiVersion,
+        endpoint: &str,
+    ) -> Result<Url, ApiError<Self::Error>> {
+        self.inner.rest_endpoint(version, endpoint)
+    }
+}
+
+#[async_trait]
+impl<C> AsyncClient for QueueClient<C>
+where
+    C: AsyncClient + Send + Sync,
+{
+    async fn rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Vec<u8>,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let path = wrapper::request_path(&request);
+        let _turn = self.queue.acquire(self.priority, path).await;
+        self.inner.rest_async(request, body).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use http::request::Builder as RequestBuilder;
+    use http::Response;
+    use tokio::time::{self, Instant};
+    use url::Url;
+
+    use crate::api::{self, v1, ApiError, ApiVersion, AsyncClient, AsyncQuery, RestClient};
+    use crate::queue::{Priority, QueueClient};
+    use crate::test::client::TestClientError;
+
+    /// Answers every request, recording when each path was requested.
+    #[derive(Clone, Default)]
+    struct Recorder {
+        calls: Arc<Mutex<Vec<(String, Instant)>>>,
+    }
+
+    impl Recorder {
+        /// The paths requested and the seconds since `start` they were requested at.
+        fn calls(&self, start: Instant) -> Vec<(String, u64)> {
+            self.calls
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(path, at)| (path.clone(), (*at - start).as_secs()))
+                .collect()
+        }
+    }
+
+    impl RestClient for Recorder {
+        type Error = TestClientError;
+
+        fn rest_endpoint(
+            &self,
+            _: ApiVersion,
+            endpoint: &str,
+        ) -> Result<Url, ApiError<Self::Error>> {
+            Ok(Url::parse("https://api.pinboard.invalid/")?.join(endpoint)?)
+        }
+    }
+
+    #[async_trait]
+    impl AsyncClient for Recorder {
+        async fn rest_async(
+            &self,
+            request: RequestBuilder,
+            _: Vec<u8>,
+        ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+            let path = request.uri_ref().unwrap().path().to_string();
+            self.calls.lock().unwrap().push((path, Instant::now()));
+            Ok(Response::new(Bytes::from_static(b"{}")))
+        }
+    }
+
+    async fn get(client: &QueueClient<Recorder>) {
+        let get = v1::tags::Get::builder().build().unwrap();
+        api::ignore(get).query_async(client).await.unwrap();
+    }
+
+    async fn recent(client: &QueueClient<Recorder>) {
+        let recent = v1::posts::Recent::builder().build().unwrap();
+        api::ignore(recent).query_async(client).await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn serializes_tasks() {
+        let recorder = Recorder::default();
+        let client = QueueClient::new(recorder.clone());
+        let start = Instant::now();
+
+        let tasks: Vec<_> = (0..3)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { get(&client).await })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let calls = recorder.calls(start);
+        let times: Vec<_> = calls.iter().map(|(_, at)| *at).collect();
+        assert_eq!(times, [0, 3, 6]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn endpoint_intervals() {
+        let recorder = Recorder::default();
+        let client = QueueClient::new(recorder.clone());
+        let start = Instant::now();
+
+        let first = client.clone();
+        let second = client.clone();
+        let recents = tokio::spawn(async move {
+            recent(&first).await;
+            recent(&first).await;
+        });
+        let other = tokio::spawn(async move {
+            time::sleep(Duration::from_secs(1)).await;
+            get(&second).await;
+        });
+        recents.await.unwrap();
+        other.await.unwrap();
+
+        assert_eq!(
+            recorder.calls(start),
+            [
+                ("/v1/posts/recent".into(), 0),
+                ("/v1/tags/get".into(), 3),
+                ("/v1/posts/recent".into(), 60),
+            ],
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn priorities() {
+        let recorder = Recorder::default();
+        let bulk = QueueClient::new(recorder.clone()).with_priority(Priority::Low);
+        let interactive = bulk.clone().with_priority(Priority::High);
+        let start = Instant::now();
+
+        get(&bulk).await;
+        let queued = {
+            let bulk = bulk.clone();
+            tokio::spawn(async move { recent(&bulk).await })
+        };
+        tokio::task::yield_now().await;
+        assert_eq!(bulk.waiting(), 1);
+        get(&interactive).await;
+        queued.await.unwrap();
+
+        assert_eq!(
+            recorder.calls(start),
+            [
+                ("/v1/tags/get".into(), 0),
+                ("/v1/tags/get".into(), 3),
+                ("/v1/posts/recent".into(), 6),
+            ],
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn dropped_requests_leave_the_queue() {
+        let recorder = Recorder::default();
+        let client = QueueClient::new(recorder.clone());
+        let start = Instant::now();
+
+        get(&client).await;
+        let cancelled = time::timeout(Duration::from_secs(1), recent(&client)).await;
+        assert!(cancelled.is_err());
+        assert_eq!(client.waiting(), 0);
+        get(&client).await;
+
+        assert_eq!(
+            recorder.calls(start),
+            [("/v1/tags/get".into(), 0), ("/v1/tags/get".into(), 3)],
+        );
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn records_limit_waits() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let metrics = DebuggingRecorder::new();
+        let snapshotter = metrics.snapshotter();
+        metrics::with_local_recorder(&metrics, || {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_time()
+                .start_paused(true)
+                .build()
+                .unwrap();
+            rt.block_on(async {
+                let client = QueueClient::new(Recorder::default());
+                get(&client).await;
+                get(&client).await;
+            });
+        });
+
+        let waits: Vec<_> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .filter(|(key, _, _, _)| key.key().name() == "pinboard_limit_wait_seconds")
+            .map(|(_, _, _, value)| value)
+            .collect();
+        // Only the second request waited for the limits.
+        assert_eq!(waits, [DebugValue::Histogram(vec![3.0.into()])]);
+    }
+}
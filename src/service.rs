@@ -0,0 +1,296 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Integration with `tower`
+//!
+//! [`ServiceClient`] turns any `tower::Service` taking an
+//! `http::Request<Vec<u8>>` and returning an `http::Response<Bytes>` into an
+//! [`AsyncClient`]. [`AsyncPinboard`] is itself such a service, so the
+//! standard `tower` layers can sit between the endpoints and the network.
+//!
+//! ```rust,no_run
+//! # #[cfg(all(feature = "async", feature = "reqwest"))]
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::time::Duration;
+//!
+//! use pinboard_rs::api::{self, v1, AsyncQuery};
+//! use pinboard_rs::service::ServiceClient;
+//! use pinboard_rs::AsyncPinboard;
+//! use tower::ServiceBuilder;
+//!
+//! let pinboard = AsyncPinboard::new("api.pinboard.in", "<TOKEN>").await?;
+//! let service = ServiceBuilder::new()
+//!     .concurrency_limit(1)
+//!     .timeout(Duration::from_secs(10))
+//!     .service(pinboard);
+//! let client = ServiceClient::new(service, "api.pinboard.in", "<TOKEN>")?;
+//!
+//! let update = v1::posts::Update::builder().build()?;
+//! api::ignore(update).query_async(&client).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::error::Error;
+use std::fmt::{self, Debug};
+#[cfg(all(feature = "async", feature = "reqwest"))]
+use std::future::Future;
+#[cfg(all(feature = "async", feature = "reqwest"))]
+use std::pin::Pin;
+#[cfg(all(feature = "async", feature = "reqwest"))]
+use std::task::{Context, Poll};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::future;
+use http::request::Builder as RequestBuilder;
+use http::{Request, Response};
+use thiserror::Error;
+use tower::Service;
+use url::Url;
+
+use crate::api::{self, ApiError, ApiVersion};
+use crate::auth::{Auth, AuthError};
+use crate::base::{base_url, endpoint_url, PinboardResult};
+#[cfg(all(feature = "async", feature = "reqwest"))]
+use crate::pinboard::RestError;
+#[cfg(all(feature = "async", feature = "reqwest"))]
+use crate::AsyncPinboard;
+
+/// Errors from a [`ServiceClient`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ServiceError {
+    /// The token could not be set on the request
+    #[error("error setting auth header: {}", source)]
+    AuthError {
+        /// The source of the error.
+        #[from]
+        source: AuthError,
+    },
+
+    /// The request could not be assembled
+    #[error("`http` error: {}", source)]
+    Http {
+        /// The source of the error.
+        #[from]
+        source: http::Error,
+    },
+
+    /// The service failed
+    #[error("service error: {}", source)]
+    Service {
+        /// The source of the error.
+        source: Box<dyn Error + Send + Sync>,
+    },
+}
+
+/// An asynchronous client which sends its requests through a `tower::Service`
+///
+/// The service must be `Clone`; each request is sent through its own clone,
+/// as is usual for `tower` services shared between tasks.
+#[derive(Clone)]
+pub struct ServiceClient<S> {
+    /// The service to send requests through
+    service: S,
+    /// The base URL for API calls
+    url: Url,
+    /// The authorization for communication with Pinboard
+    auth: Auth,
+}
+
+impl<S> Debug for ServiceClient<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ServiceClient")
+            .field("url", &self.url)
+            .finish()
+    }
+}
+
+impl<S> ServiceClient<S> {
+    /// Create a client for the API at `host` which sends requests through `service`
+    ///
+    /// The `token` is the personal access token available at <https://pinboard.in>
+    pub fn new<H, T>(service: S, host: H, token: T) -> PinboardResult<Self>
+    where
+        H: AsRef<str>,
+        T: Into<String>,
+    {
+        let url = base_url("https", host.as_ref())?;
+        Ok(ServiceClient {
+            service,
+            url,
+            auth: Auth::Token(token.into()),
+        })
+    }
+
+    /// The service requests are sent through.
+    pub fn service(&self) -> &S {
+        &self.service
+    }
+}
+
+impl<S> api::RestClient for ServiceClient<S> {
+    type Error = ServiceError;
+
+    fn rest_endpoint(
+        &self,
+        version: ApiVersion,
+        endpoint: &str,
+    ) -> Result<Url, ApiError<Self::Error>> {
+        Ok(endpoint_url(&self.url, &self.auth, version, endpoint)?)
+    }
+}
+
+#[async_trait]
+impl<S> api::AsyncClient for ServiceClient<S>
+where
+    S: Service<Request<Vec<u8>>, Response = Response<Bytes>> + Clone + Send + Sync,
+    S::Error: Into<Box<dyn Error + Send + Sync>>,
+    S::Future: Send,
+{
+    async fn rest_async(
+        &self,
+        mut request: RequestBuilder,
+        body: Vec<u8>,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let call = || async {
+            self.auth.set_header(request.headers_mut().unwrap())?;
+            let request = request.body(body)?;
+
+            let mut service = self.service.clone();
+            let service_error = |err: S::Error| ServiceError::Service { source: err.into() };
+            future::poll_fn(|cx| service.poll_ready(cx))
+                .await
+                .map_err(service_error)?;
+            service.call(request).await.map_err(service_error)
+        };
+        call().await.map_err(ApiError::client)
+    }
+}
+
+#[cfg(all(feature = "async", feature = "reqwest"))]
+impl Service<Request<Vec<u8>>> for AsyncPinboard {
+    type Response = Response<Bytes>;
+    type Error = RestError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    /// Send the request with the token attached.
+    ///
+    /// V1 endpoints also expect the token in the query string; URLs from
+    /// `RestClient::rest_endpoint` carry it.
+    fn call(&mut self, request: Request<Vec<u8>>) -> Self::Future {
+        let pinboard = self.clone();
+        Box::pin(async move { pinboard.send(request).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use bytes::Bytes;
+    use http::{Request, Response, StatusCode};
+    use tower::{service_fn, ServiceBuilder};
+
+    use crate::api::{self, v1, ApiError, AsyncQuery};
+    use crate::service::{ServiceClient, ServiceError};
+
+    #[tokio::test]
+    async fn query_through_service() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let record = Arc::clone(&seen);
+        let service = service_fn(move |request: Request<Vec<u8>>| {
+            let record = Arc::clone(&record);
+            async move {
+                let token = request.headers()["x-auth-token"].clone();
+                record
+                    .lock()
+                    .unwrap()
+                    .push((request.uri().to_string(), token));
+                Ok::<_, Infallible>(Response::new(Bytes::from_static(b"{}")))
+            }
+        });
+        let client = ServiceClient::new(service, "api.pinboard.invalid", "user:TOKEN").unwrap();
+
+        let get = v1::tags::Get::builder().build().unwrap();
+        api::ignore(get).query_async(&client).await.unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(
+            seen[0].0,
+            "https://api.pinboard.invalid/v1/tags/get?auth_token=user%3ATOKEN&format=json",
+        );
+        assert_eq!(seen[0].1, "user:TOKEN");
+    }
+
+    #[tokio::test]
+    async fn status_is_checked() {
+        let service = service_fn(|_: Request<Vec<u8>>| async {
+            let mut rsp = Response::new(Bytes::new());
+            *rsp.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+            Ok::<_, Infallible>(rsp)
+        });
+        let client = ServiceClient::new(service, "api.pinboard.invalid", "user:TOKEN").unwrap();
+
+        let get = v1::tags::Get::builder().build().unwrap();
+        let err = api::ignore(get).query_async(&client).await.unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+    }
+
+    #[tokio::test]
+    async fn layer_errors() {
+        let service = ServiceBuilder::new()
+            .timeout(Duration::from_millis(10))
+            .service(service_fn(|_: Request<Vec<u8>>| async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                Ok::<_, Infallible>(Response::new(Bytes::new()))
+            }));
+        let client = ServiceClient::new(service, "api.pinboard.invalid", "user:TOKEN").unwrap();
+
+        let get = v1::tags::Get::builder().build().unwrap();
+        let err = api::ignore(get).query_async(&client).await.unwrap_err();
+        if let ApiError::Client {
+            source: ServiceError::Service { source },
+        } = err
+        {
+            assert!(source.is::<tower::timeout::error::Elapsed>());
+        } else {
+            panic!("unexpected error: {}", err);
+        }
+    }
+
+    #[cfg(all(feature = "async", feature = "reqwest", feature = "testing"))]
+    #[tokio::test]
+    async fn async_pinboard_service() {
+        use http::Method;
+        use tower::ServiceExt;
+
+        use crate::api::{ApiVersion, RestClient};
+        use crate::testing::FakePinboard;
+
+        let fake = FakePinboard::start("user:TOKEN").unwrap();
+        let pinboard = fake.async_client().await;
+        let url = pinboard
+            .rest_endpoint(ApiVersion::V1, "v1/posts/update")
+            .unwrap();
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(url.as_str())
+            .body(Vec::new())
+            .unwrap();
+
+        let rsp = pinboard.oneshot(request).await.unwrap();
+        assert_eq!(rsp.status(), StatusCode::OK);
+    }
+}
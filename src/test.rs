@@ -5,5 +5,6 @@
 // except according to those terms.
 
 pub mod client;
+pub mod middleware;
 
 //mod types;
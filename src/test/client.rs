@@ -16,7 +16,7 @@ use serde::ser::Serialize;
 use thiserror::Error;
 use url::Url;
 
-use crate::api::{ApiError, AsyncClient, Client, RestClient};
+use crate::api::{ApiError, ApiVersion, AsyncClient, Client, RestClient};
 
 #[derive(Debug, Builder)]
 pub struct ExpectedUrl {
@@ -41,7 +41,6 @@ impl ExpectedUrlBuilder {
         self
     }
 
-    #[allow(dead_code)]
     pub fn body_str(&mut self, body: &str) -> &mut Self {
         self.body = Some(body.bytes().collect());
         self
@@ -143,8 +142,15 @@ pub enum TestClientError {}
 impl RestClient for SingleTestClient {
     type Error = TestClientError;
 
-    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
-        Ok(Url::parse(&format!("{}/{}", CLIENT_STUB, endpoint))?)
+    fn rest_endpoint(
+        &self,
+        version: ApiVersion,
+        endpoint: &str,
+    ) -> Result<Url, ApiError<Self::Error>> {
+        match version {
+            ApiVersion::V2 => Ok(Url::parse(&format!("{}/v2/{}", CLIENT_STUB, endpoint))?),
+            _ => Ok(Url::parse(&format!("{}/{}", CLIENT_STUB, endpoint))?),
+        }
     }
 }
 
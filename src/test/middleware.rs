@@ -0,0 +1,51 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{Request, Response};
+
+use crate::api;
+use crate::middleware::{AsyncNext, BoxError, Middleware, Next};
+
+/// Sends a request again, up to the given number of times, while it gets a
+/// server error.
+pub struct Retry(pub u32);
+
+#[async_trait]
+impl Middleware for Retry {
+    fn handle(
+        &self,
+        request: &mut Request<Vec<u8>>,
+        next: Next<'_>,
+    ) -> Result<Response<Bytes>, BoxError> {
+        let mut rsp = next.run(request)?;
+        for retries in 1..=self.0 {
+            if !rsp.status().is_server_error() {
+                break;
+            }
+            api::record_retries(retries);
+            rsp = next.run(request)?;
+        }
+        Ok(rsp)
+    }
+
+    async fn handle_async(
+        &self,
+        request: &mut Request<Vec<u8>>,
+        next: AsyncNext<'_>,
+    ) -> Result<Response<Bytes>, BoxError> {
+        let mut rsp = next.run(request).await?;
+        for retries in 1..=self.0 {
+            if !rsp.status().is_server_error() {
+                break;
+            }
+            api::record_retries(retries);
+            rsp = next.run(request).await?;
+        }
+        Ok(rsp)
+    }
+}
@@ -0,0 +1,428 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A fake Pinboard server for integration tests
+//!
+//! [`FakePinboard`] runs a small HTTP server on the loopback interface
+//! which emulates the v1 API over an in-memory dataset. Posts, tags and
+//! notes behave like they do on pinboard.in, and calls made faster than
+//! the [`Limit`](crate::api::v1::Limit) values allow are answered with
+//! `429 Too Many Requests`. It is available with the `testing` feature.
+//!
+//! ```rust
+//! # fn main() {
+//! use pinboard_rs::api::{v1::posts, Query};
+//! use pinboard_rs::testing::FakePinboard;
+//! use pinboard_rs::types::v1::PostsRecent;
+//! use url::Url;
+//!
+//! let server = FakePinboard::start("user:0123456789ABCDEF").unwrap();
+//! server.set_rate_limits(false);
+//! let client = server.client();
+//!
+//! let add = posts::Add::builder()
+//!     .url(Url::parse("https://example.com/").unwrap())
+//!     .description("Example")
+//!     .build()
+//!     .unwrap();
+//! pinboard_rs::api::ignore(add).query(&client).unwrap();
+//!
+//! let recent: PostsRecent = posts::Recent::builder().build().unwrap().query(&client).unwrap();
+//! assert_eq!(recent.posts.len(), 1);
+//! # }
+//! ```
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+
+use url::Url;
+
+use crate::types::v1::{Note, Post};
+use crate::wrapper;
+#[cfg(feature = "async")]
+use crate::AsyncPinboard;
+use crate::Pinboard;
+
+mod state;
+
+use self::state::State;
+
+/// An in-process server emulating the Pinboard v1 API
+///
+/// The server shuts down when dropped.
+pub struct FakePinboard {
+    addr: SocketAddr,
+    token: String,
+    state: Arc<Mutex<State>>,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FakePinboard {
+    /// Start a server accepting `token` on a free local port.
+    ///
+    /// The token takes the usual `user:SECRET` form.
+    pub fn start<T>(token: T) -> io::Result<Self>
+    where
+        T: Into<String>,
+    {
+        let token = token.into();
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State::new(token.clone())));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let state = Arc::clone(&state);
+            let shutdown = Arc::clone(&shutdown);
+            thread::spawn(move || accept(listener, state, shutdown))
+        };
+
+        Ok(FakePinboard {
+            addr,
+            token,
+            state,
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+
+    /// The `host:port` the server listens on.
+    pub fn host(&self) -> String {
+        self.addr.to_string()
+    }
+
+    /// The token the server accepts.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// A client connected to the server.
+    pub fn client(&self) -> Pinboard {
+        Pinboard::new_insecure(self.host(), self.token.clone())
+            .expect("the server address should be a valid host")
+    }
+
+    /// An async client connected to the server.
+    #[cfg(feature = "async")]
+    pub async fn async_client(&self) -> AsyncPinboard {
+        AsyncPinboard::new_insecure(self.host(), self.token.clone())
+            .await
+            .expect("the server address should be a valid host")
+    }
+
+    /// Whether calls made faster than the API limits are rejected.
+    ///
+    /// Limits are enforced by default.
+    pub fn set_rate_limits(&self, enforce: bool) {
+        self.state().rate_limits = enforce;
+    }
+
+    /// Add a post to the dataset, replacing any post with the same URL.
+    pub fn add_post(&self, post: Post) {
+        self.state().insert_post(post);
+    }
+
+    /// Add a note to the dataset.
+    pub fn add_note(&self, note: Note) {
+        self.state().notes.push(note);
+    }
+
+    /// The posts in the dataset, newest first.
+    pub fn posts(&self) -> Vec<Post> {
+        self.state().posts.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        wrapper::lock(&self.state)
+    }
+}
+
+impl Drop for FakePinboard {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake up the accept loop so it notices the shutdown.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn accept(listener: TcpListener, state: Arc<Mutex<State>>, shutdown: Arc<AtomicBool>) {
+    for stream in listener.incoming() {
+        if shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+        if let Ok(stream) = stream {
+            let state = Arc::clone(&state);
+            thread::spawn(move || {
+                let _ = serve(stream, &state);
+            });
+        }
+    }
+}
+
+fn serve(mut stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let target = match line.split_whitespace().nth(1) {
+        Some(target) => target.to_string(),
+        None => return Ok(()),
+    };
+
+    let mut length = 0;
+    let mut header_token = None;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("x-auth-token") {
+                header_token = Some(value.to_string());
+            }
+        }
+    }
+    // The v1 API only reads query parameters; drain any body.
+    io::copy(&mut reader.by_ref().take(length), &mut io::sink())?;
+
+    let url = Url::parse("http://localhost")
+        .and_then(|base| base.join(&target))
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let params = url.query_pairs().into_owned().collect();
+    let reply = wrapper::lock(state).handle(url.path(), params, header_token.as_deref());
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        reply.status,
+        reply.content_type,
+        reply.body.len(),
+    )?;
+    if let Some(secs) = reply.retry_after {
+        write!(stream, "Retry-After: {}\r\n", secs)?;
+    }
+    stream.write_all(b"\r\n")?;
+    stream.write_all(&reply.body)?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use http::StatusCode;
+    use serde_json::{json, Value};
+
+    use crate::api::v1::{notes, posts, tags};
+    use crate::api::{self, Query};
+    use crate::testing::FakePinboard;
+    #[cfg(feature = "async")]
+    use crate::types::v1::PostsRecent;
+    use crate::types::v1::{Note, NoteList, Post, Posts, PostsAdd, PostsList, Tags};
+    use url::Url;
+
+    const TOKEN: &str = "user:0123456789ABCDEF";
+
+    fn post(href: &str, tags: &str, day: u32) -> Post {
+        Post {
+            href: href.into(),
+            description: href.into(),
+            extended: String::new(),
+            meta: String::new(),
+            hash: String::new(),
+            time: Utc.with_ymd_and_hms(2024, 1, day, 12, 0, 0).unwrap(),
+            shared: "yes".into(),
+            toread: "no".into(),
+            tags: tags.into(),
+        }
+    }
+
+    fn server() -> FakePinboard {
+        let server = FakePinboard::start(TOKEN).unwrap();
+        server.set_rate_limits(false);
+        server.add_post(post("https://a.example/", "rust web", 1));
+        server.add_post(post("https://b.example/", "rust", 2));
+        server.add_post(post("https://c.example/", "cooking", 3));
+        server
+    }
+
+    #[test]
+    fn test_posts_add_and_delete() {
+        let server = server();
+        let client = server.client();
+
+        let add = posts::Add::builder()
+            .url(Url::parse("https://d.example/").unwrap())
+            .description("D")
+            .tags(&["new"][..])
+            .build()
+            .unwrap();
+        api::ignore(add).query(&client).unwrap();
+        assert_eq!(server.posts()[0].href, "https://d.example/");
+        assert_eq!(server.posts()[0].tags, "new");
+
+        let add = posts::Add::builder()
+            .url(Url::parse("https://d.example/").unwrap())
+            .description("D")
+            .replace(false)
+            .build()
+            .unwrap();
+        let rsp: PostsAdd = add.query(&client).unwrap();
+        assert_eq!(rsp.result_code, "item already exists");
+
+        let delete = posts::Delete::builder()
+            .url(Url::parse("https://d.example/").unwrap())
+            .build()
+            .unwrap();
+        api::ignore(delete).query(&client).unwrap();
+        assert_eq!(server.posts().len(), 3);
+    }
+
+    #[test]
+    fn test_posts_all_filters() {
+        let server = server();
+        let client = server.client();
+
+        let all = posts::All::builder().tags(&["rust"][..]).build().unwrap();
+        let posts: Posts = all.query(&client).unwrap();
+        let hrefs: Vec<_> = posts.iter().map(|p| p.href.as_str()).collect();
+        assert_eq!(hrefs, ["https://b.example/", "https://a.example/"]);
+
+        let all = posts::All::builder().start(1).results(1).build().unwrap();
+        let posts: Posts = all.query(&client).unwrap();
+        assert_eq!(posts[0].href, "https://b.example/");
+    }
+
+    #[test]
+    fn test_posts_get() {
+        let server = server();
+        let client = server.client();
+
+        let get = posts::Get::builder()
+            .url(Url::parse("https://a.example/").unwrap())
+            .build()
+            .unwrap();
+        let list: PostsList = get.query(&client).unwrap();
+        assert_eq!(list.posts.len(), 1);
+        assert_eq!(list.posts[0].tags, "rust web");
+
+        let get = posts::Get::builder()
+            .dt(NaiveDate::from_ymd_opt(2024, 1, 2).unwrap())
+            .build()
+            .unwrap();
+        let list: PostsList = get.query(&client).unwrap();
+        let hrefs: Vec<_> = list.posts.iter().map(|p| p.href.as_str()).collect();
+        assert_eq!(hrefs, ["https://b.example/"]);
+    }
+
+    #[test]
+    fn test_posts_dates() {
+        let server = server();
+        let client = server.client();
+
+        let dates = posts::Dates::builder().tags(&["rust"][..]).build().unwrap();
+        let dates: Value = dates.query(&client).unwrap();
+        assert_eq!(dates["tag"], "rust");
+        assert_eq!(dates["dates"], json!({"2024-01-01": 1, "2024-01-02": 1}));
+    }
+
+    #[test]
+    fn test_tags_rename() {
+        let server = server();
+        let client = server.client();
+
+        let rename = tags::Rename::builder()
+            .old("rust")
+            .new("rustlang")
+            .build()
+            .unwrap();
+        api::ignore(rename).query(&client).unwrap();
+
+        let tags: Tags = tags::Get::builder()
+            .build()
+            .unwrap()
+            .query(&client)
+            .unwrap();
+        assert_eq!(tags.get("rustlang"), Some(&2));
+        assert_eq!(tags.get("rust"), None);
+    }
+
+    #[test]
+    fn test_notes() {
+        let server = server();
+        server.add_note(Note {
+            id: "abc".into(),
+            hash: "0123".into(),
+            title: "Note".into(),
+            text: "Some text".into(),
+            length: 9,
+            created_at: "2024-01-01 12:00:00".into(),
+            updated_at: "2024-01-01 12:00:00".into(),
+        });
+        let client = server.client();
+
+        let list: NoteList = notes::List::builder()
+            .build()
+            .unwrap()
+            .query(&client)
+            .unwrap();
+        assert_eq!(list.count, 1);
+
+        let note: Note = notes::Note::builder()
+            .id("abc")
+            .build()
+            .unwrap()
+            .query(&client)
+            .unwrap();
+        assert_eq!(note.text, "Some text");
+    }
+
+    #[test]
+    fn test_bad_token() {
+        let server = server();
+        let client = crate::Pinboard::new_insecure(server.host(), "user:WRONG").unwrap();
+
+        let update = posts::Update::builder().build().unwrap();
+        let err = api::ignore(update).query(&client).unwrap_err();
+        assert!(err.is_auth_failure());
+        assert_eq!(err.status(), Some(StatusCode::UNAUTHORIZED));
+    }
+
+    #[test]
+    fn test_rate_limits() {
+        let server = server();
+        server.set_rate_limits(true);
+        let client = server.client();
+
+        let update = || posts::Update::builder().build().unwrap();
+        api::raw(update()).query(&client).unwrap();
+        let err = api::raw(update()).query(&client).unwrap_err();
+        assert!(err.is_rate_limited());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_recent() {
+        use crate::api::AsyncQuery;
+
+        let server = server();
+        let client = server.async_client().await;
+
+        let recent = posts::Recent::builder().count(2).build().unwrap();
+        let recent: PostsRecent = recent.query_async(&client).await.unwrap();
+        let hrefs: Vec<_> = recent.posts.iter().map(|p| p.href.as_str()).collect();
+        assert_eq!(hrefs, ["https://c.example/", "https://b.example/"]);
+    }
+}
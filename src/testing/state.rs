@@ -0,0 +1,424 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use http::StatusCode;
+use serde_json::{json, Value};
+
+use crate::api::v1::posts;
+use crate::types::v1::{Note, Post};
+use crate::wrapper;
+
+/// The response to a single request
+pub(crate) struct Reply {
+    pub status: StatusCode,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+    pub retry_after: Option<u64>,
+}
+
+impl Reply {
+    fn json(value: Value) -> Self {
+        Reply {
+            status: StatusCode::OK,
+            content_type: "application/json",
+            body: value.to_string().into_bytes(),
+            retry_after: None,
+        }
+    }
+
+    fn text(status: StatusCode, text: &str) -> Self {
+        Reply {
+            status,
+            content_type: "text/plain",
+            body: text.as_bytes().into(),
+            retry_after: None,
+        }
+    }
+
+    fn result_code(code: &str) -> Self {
+        Self::json(json!({ "result_code": code }))
+    }
+
+    fn done() -> Self {
+        Self::json(json!({ "result": "done" }))
+    }
+}
+
+/// The in-memory account behind a fake server
+pub(crate) struct State {
+    token: String,
+    user: String,
+    pub(crate) posts: Vec<Post>,
+    pub(crate) notes: Vec<Note>,
+    pub(crate) rate_limits: bool,
+    update_time: DateTime<Utc>,
+    last_call: Option<Instant>,
+    last_call_by_endpoint: HashMap<String, Instant>,
+}
+
+type Params = Vec<(String, String)>;
+
+fn param<'a>(params: &'a Params, key: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.as_str())
+        .filter(|v| !v.is_empty())
+}
+
+fn tags_of(post: &Post) -> impl Iterator<Item = &str> {
+    post.tags.split_whitespace()
+}
+
+fn has_tags(post: &Post, filter: &[&str]) -> bool {
+    filter.iter().all(|tag| tags_of(post).any(|t| t == *tag))
+}
+
+fn tag_filter(params: &Params) -> Vec<&str> {
+    param(params, "tag")
+        .map(|tags| tags.split_whitespace().collect())
+        .unwrap_or_default()
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|dt| dt.and_utc())
+        })
+}
+
+fn digest<T: Hash>(value: T) -> String {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+fn yes_no(value: Option<&str>, default: &str) -> String {
+    match value {
+        Some("yes") | Some("true") => "yes".into(),
+        Some("no") | Some("false") => "no".into(),
+        _ => default.into(),
+    }
+}
+
+// The minimum time between calls to `endpoint`
+fn limit_for(endpoint: &str) -> Duration {
+    wrapper::endpoint_intervals()
+        .into_iter()
+        .find(|(path, _)| *path == endpoint)
+        .map_or_else(wrapper::interval::<posts::Update>, |(_, interval)| interval)
+}
+
+impl State {
+    pub(crate) fn new(token: String) -> Self {
+        let user = token.split(':').next().unwrap_or_default().into();
+        State {
+            token,
+            user,
+            posts: Vec::new(),
+            notes: Vec::new(),
+            rate_limits: true,
+            update_time: Utc::now(),
+            last_call: None,
+            last_call_by_endpoint: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn insert_post(&mut self, post: Post) {
+        self.posts.retain(|p| p.href != post.href);
+        self.posts.push(post);
+        self.posts.sort_by_key(|p| std::cmp::Reverse(p.time));
+        self.update_time = Utc::now();
+    }
+
+    // The time to wait before `endpoint` may be called again
+    fn wait_for(&self, endpoint: &str, now: Instant) -> Option<Duration> {
+        let general = self
+            .last_call
+            .and_then(|last| limit_for("").checked_sub(now - last));
+        let specific = self
+            .last_call_by_endpoint
+            .get(endpoint)
+            .and_then(|last| limit_for(endpoint).checked_sub(now - *last));
+        general.max(specific).filter(|wait| !wait.is_zero())
+    }
+
+    pub(crate) fn handle(
+        &mut self,
+        path: &str,
+        params: Params,
+        header_token: Option<&str>,
+    ) -> Reply {
+        let endpoint = path.trim_start_matches('/');
+
+        let token = param(&params, "auth_token").or(header_token);
+        if token != Some(self.token.as_str()) {
+            return Reply::text(StatusCode::UNAUTHORIZED, "401 Unauthorized");
+        }
+
+        if self.rate_limits {
+            let now = Instant::now();
+            if let Some(wait) = self.wait_for(endpoint, now) {
+                let mut reply = Reply::text(StatusCode::TOO_MANY_REQUESTS, "Too Many Requests");
+                reply.retry_after = Some(wait.as_secs() + 1);
+                return reply;
+            }
+            self.last_call = Some(now);
+            self.last_call_by_endpoint.insert(endpoint.into(), now);
+        }
+
+        match endpoint {
+            "v1/posts/update" => Reply::json(json!({ "update_time": self.update_time })),
+            "v1/posts/add" => self.posts_add(&params),
+            "v1/posts/delete" => self.posts_delete(&params),
+            "v1/posts/get" => self.posts_get(&params),
+            "v1/posts/recent" => self.posts_recent(&params),
+            "v1/posts/dates" => self.posts_dates(&params),
+            "v1/posts/all" => self.posts_all(&params),
+            "v1/tags/get" => self.tags_get(),
+            "v1/tags/rename" => self.tags_rename(&params),
+            "v1/tags/delete" => self.tags_delete(&params),
+            "v1/notes/list" => self.notes_list(),
+            "v1/user/api_token" => {
+                let secret = self.token.split(':').nth(1).unwrap_or_default();
+                Reply::json(json!({ "result": secret }))
+            },
+            "v1/user/secret" => Reply::json(json!({ "result": digest(&self.token) })),
+            _ => {
+                match endpoint
+                    .strip_prefix("v1/notes/")
+                    .and_then(|id| id.strip_suffix('/'))
+                {
+                    Some(id) => self.notes_get(id),
+                    None => Reply::text(StatusCode::NOT_FOUND, "404 Not Found"),
+                }
+            },
+        }
+    }
+
+    fn posts_add(&mut self, params: &Params) -> Reply {
+        let href = match param(params, "url") {
+            Some(href) => href,
+            None => return Reply::result_code("missing url"),
+        };
+        let description = match param(params, "description") {
+            Some(description) => description,
+            None => return Reply::result_code("missing description"),
+        };
+        if yes_no(param(params, "replace"), "yes") == "no"
+            && self.posts.iter().any(|p| p.href == href)
+        {
+            return Reply::result_code("item already exists");
+        }
+        let time = match param(params, "dt") {
+            Some(dt) => match parse_time(dt) {
+                Some(time) => time,
+                None => return Reply::result_code("invalid date"),
+            },
+            None => Utc::now(),
+        };
+
+        let mut post = Post {
+            href: href.into(),
+            description: description.into(),
+            extended: param(params, "extended").unwrap_or_default().into(),
+            meta: String::new(),
+            hash: digest(href),
+            time,
+            shared: yes_no(param(params, "shared"), "yes"),
+            toread: yes_no(param(params, "toread"), "no"),
+            tags: param(params, "tags").unwrap_or_default().into(),
+        };
+        post.meta = digest((&post.description, &post.extended, &post.tags, time));
+        self.insert_post(post);
+
+        Reply::result_code("done")
+    }
+
+    fn posts_delete(&mut self, params: &Params) -> Reply {
+        let href = match param(params, "url") {
+            Some(href) => href,
+            None => return Reply::result_code("missing url"),
+        };
+        let count = self.posts.len();
+        self.posts.retain(|p| p.href != href);
+        if self.posts.len() == count {
+            return Reply::result_code("item not found");
+        }
+        self.update_time = Utc::now();
+        Reply::result_code("done")
+    }
+
+    fn posts_get(&self, params: &Params) -> Reply {
+        let tags = tag_filter(params);
+        let href = param(params, "url");
+        let date = match param(params, "dt") {
+            Some(dt) => parse_time(dt).map(|t| t.date_naive()),
+            None if href.is_none() => self.posts.first().map(|p| p.time.date_naive()),
+            None => None,
+        };
+
+        let posts: Vec<&Post> = self
+            .posts
+            .iter()
+            .filter(|p| has_tags(p, &tags))
+            .filter(|p| href.map_or(true, |href| p.href == href))
+            .filter(|p| date.map_or(true, |date| p.time.date_naive() == date))
+            .collect();
+        let date = posts.first().map_or_else(Utc::now, |p| p.time);
+
+        Reply::json(json!({
+            "date": date,
+            "user": self.user,
+            "tag": tags.join(" "),
+            "posts": posts,
+        }))
+    }
+
+    fn posts_recent(&self, params: &Params) -> Reply {
+        let tags = tag_filter(params);
+        let count = param(params, "count")
+            .and_then(|c| c.parse::<usize>().ok())
+            .unwrap_or(15)
+            .min(100);
+
+        let posts: Vec<&Post> = self
+            .posts
+            .iter()
+            .filter(|p| has_tags(p, &tags))
+            .take(count)
+            .collect();
+        let date = posts.first().map_or_else(Utc::now, |p| p.time);
+
+        Reply::json(json!({
+            "date": date,
+            "user": self.user,
+            "posts": posts,
+        }))
+    }
+
+    fn posts_dates(&self, params: &Params) -> Reply {
+        let tags = tag_filter(params);
+        let mut dates = BTreeMap::new();
+        for post in self.posts.iter().filter(|p| has_tags(p, &tags)) {
+            *dates
+                .entry(post.time.format("%Y-%m-%d").to_string())
+                .or_insert(0) += 1;
+        }
+
+        Reply::json(json!({
+            "user": self.user,
+            "tag": tags.join(" "),
+            "dates": dates,
+        }))
+    }
+
+    fn posts_all(&self, params: &Params) -> Reply {
+        let tags = tag_filter(params);
+        let start = param(params, "start")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        let results = param(params, "results")
+            .and_then(|r| r.parse().ok())
+            .unwrap_or(usize::MAX);
+        let fromdt = param(params, "fromdt").and_then(parse_time);
+        // A bare date includes the whole day.
+        let todt = param(params, "todt").and_then(|dt| {
+            let end = parse_time(dt)?;
+            Some(if dt.contains('T') {
+                end
+            } else {
+                end + chrono::Duration::days(1)
+            })
+        });
+
+        let posts: Vec<&Post> = self
+            .posts
+            .iter()
+            .filter(|p| has_tags(p, &tags))
+            .filter(|p| fromdt.map_or(true, |dt| p.time >= dt))
+            .filter(|p| todt.map_or(true, |dt| p.time < dt))
+            .skip(start)
+            .take(results)
+            .collect();
+
+        Reply::json(json!(posts))
+    }
+
+    fn tags_get(&self) -> Reply {
+        let mut tags = BTreeMap::new();
+        for tag in self.posts.iter().flat_map(tags_of) {
+            *tags.entry(tag).or_insert(0) += 1;
+        }
+        Reply::json(json!(tags))
+    }
+
+    fn retag<F>(&mut self, f: F)
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        for post in &mut self.posts {
+            let tags: Vec<String> = post.tags.split_whitespace().filter_map(&f).collect();
+            post.tags = tags.join(" ");
+        }
+        self.update_time = Utc::now();
+    }
+
+    fn tags_rename(&mut self, params: &Params) -> Reply {
+        let (old, new) = match (param(params, "old"), param(params, "new")) {
+            (Some(old), Some(new)) => (old.to_string(), new.to_string()),
+            _ => return Reply::json(json!({ "result": "missing old or new tag" })),
+        };
+        self.retag(|tag| Some(if tag == old { new.clone() } else { tag.into() }));
+        Reply::done()
+    }
+
+    fn tags_delete(&mut self, params: &Params) -> Reply {
+        let old = match param(params, "tag") {
+            Some(old) => old.to_string(),
+            None => return Reply::json(json!({ "result": "missing tag" })),
+        };
+        self.retag(|tag| if tag == old { None } else { Some(tag.into()) });
+        Reply::done()
+    }
+
+    fn notes_list(&self) -> Reply {
+        let notes: Vec<Value> = self
+            .notes
+            .iter()
+            .map(|note| {
+                json!({
+                    "id": note.id,
+                    "hash": note.hash,
+                    "title": note.title,
+                    "length": note.length,
+                    "created_at": note.created_at,
+                    "updated_at": note.updated_at,
+                })
+            })
+            .collect();
+
+        Reply::json(json!({ "count": notes.len(), "notes": notes }))
+    }
+
+    fn notes_get(&self, id: &str) -> Reply {
+        match self.notes.iter().find(|note| note.id == id) {
+            Some(note) => Reply::json(json!(note)),
+            None => Reply::text(StatusCode::NOT_FOUND, "404 Not Found"),
+        }
+    }
+}
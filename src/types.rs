@@ -13,6 +13,7 @@
 //! upstream.
 
 pub mod v1;
+pub mod v2;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -0,0 +1,338 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The API token returned by the `auth` endpoint
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuthToken {
+    pub token: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Awesome {
+    pub awesome: bool,
+}
+
+/// A bookmark of the user
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+    /// The identifier of the bookmark
+    pub id: String,
+    /// The bookmarked URL
+    pub url: String,
+    /// The title of the bookmark
+    pub title: String,
+    /// The extended description of the bookmark
+    pub description: String,
+    /// The tags of the bookmark
+    pub tags: Vec<String>,
+    /// When the bookmark was saved
+    pub created_at: DateTime<Utc>,
+    /// When the bookmark was last changed
+    pub updated_at: DateTime<Utc>,
+    /// Whether only the user can see the bookmark
+    pub private: bool,
+    /// Whether the bookmark is marked to read later
+    pub unread: bool,
+}
+
+/// A named group of tags
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Bundle {
+    /// The name of the bundle
+    pub name: String,
+    /// The tags in the bundle
+    pub tags: Vec<String>,
+    /// When the bundle was created
+    pub created_at: DateTime<Utc>,
+    /// When the bundle was last changed
+    pub updated_at: DateTime<Utc>,
+}
+
+pub type Bundles = Vec<Bundle>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Hello {
+    pub message: String,
+}
+
+/// The response of the `last_update` endpoint
+pub type LastUpdate = crate::types::Update;
+
+/// A URL from the site-wide popular feed
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PopularBookmark {
+    pub url: String,
+    pub title: String,
+    /// The most used tags for the URL
+    pub tags: Vec<String>,
+    /// The number of users who saved the URL
+    pub saves: usize,
+}
+
+pub type PopularBookmarks = Vec<PopularBookmark>;
+
+/// A public bookmark saved by another user
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PublicBookmark {
+    pub user: String,
+    pub url: String,
+    pub title: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub type PublicBookmarks = Vec<PublicBookmark>;
+
+/// A single change to a bookmark
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum DeltaEvent {
+    /// A bookmark was added
+    Add { bookmark: Bookmark },
+    /// A bookmark was changed
+    Update { bookmark: Bookmark },
+    /// A bookmark was deleted
+    Delete { id: String, url: String },
+}
+
+/// A page of changes from the delta endpoint
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DeltaPage {
+    /// The cursor to request the following changes
+    pub cursor: String,
+    /// Whether more changes are waiting past this cursor
+    pub more: bool,
+    pub events: Vec<DeltaEvent>,
+}
+
+/// A page of search results
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SearchResults {
+    /// The search query as understood by Pinboard
+    pub query: String,
+    /// The total number of matching bookmarks
+    pub total: usize,
+    /// The offset of this page
+    pub start: usize,
+    pub bookmarks: Vec<Bookmark>,
+}
+
+impl SearchResults {
+    /// The `start` value for the next page, if there is one
+    pub fn next_start(&self) -> Option<usize> {
+        let next = self.start + self.bookmarks.len();
+        if self.bookmarks.is_empty() || next >= self.total {
+            None
+        } else {
+            Some(next)
+        }
+    }
+}
+
+/// A followed user, tag, or tag from a single user
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Subscription {
+    pub user: Option<String>,
+    pub tag: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub type Subscriptions = Vec<Subscription>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Tab {
+    pub title: String,
+    pub url: String,
+}
+
+/// The tabs of each browser window
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TabSet {
+    pub windows: Vec<Vec<Tab>>,
+}
+
+/// A saved tab set, without its tabs
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TabSetSummary {
+    pub id: String,
+    pub title: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// The number of windows in the set
+    pub windows: usize,
+    /// The number of tabs across all windows
+    pub tabs: usize,
+}
+
+pub type TabSetSummaries = Vec<TabSetSummary>;
+
+/// A tag and how often it is used
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    /// The name of the tag
+    pub name: String,
+    /// The number of bookmarks with the tag
+    pub count: usize,
+}
+
+/// The tags of the user
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TagList {
+    /// The number of tags
+    pub count: usize,
+    /// The tags with their use counts
+    pub tags: Vec<Tag>,
+}
+
+/// The details of a single tag
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TagInfo {
+    /// The name of the tag
+    pub name: String,
+    /// The number of bookmarks with the tag
+    pub count: usize,
+    /// The number of public bookmarks with the tag
+    pub public_count: usize,
+    /// The number of private bookmarks with the tag
+    pub private_count: usize,
+    /// When the tag was first used, if it is in use
+    pub first_used: Option<DateTime<Utc>>,
+    /// When the tag was last used, if it is in use
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+/// The result of a rename, merge or delete of tags
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TagsChanged {
+    /// The tags that were changed
+    pub tags: Vec<String>,
+    /// The number of bookmarks that were updated
+    pub bookmarks: usize,
+}
+
+/// What Pinboard knows about a URL
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UrlInfo {
+    pub url: String,
+    pub title: Option<String>,
+    /// The number of users who saved the URL
+    pub saves: usize,
+    pub popular_tags: Vec<Tag>,
+}
+
+/// A user who publicly saved a URL
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UrlSaver {
+    pub user: String,
+    pub created_at: DateTime<Utc>,
+    pub tags: Vec<String>,
+}
+
+pub type UrlSavers = Vec<UrlSaver>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UserProfile {
+    pub username: String,
+    pub full_name: String,
+    pub created_at: DateTime<Utc>,
+    pub private_by_default: bool,
+    pub public_profile: bool,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BOOKMARK: &str = r#"{"id":"abc123","url":"https://some.web.site","title":"Words","description":"More Words","tags":["one","two"],"created_at":"2024-01-01T00:00:00Z","updated_at":"2024-01-02T00:00:00Z","private":false,"unread":true}"#;
+
+    #[test]
+    fn test_delta_page() {
+        let body = format!(
+            r#"{{"cursor":"c-9","more":true,"events":[{{"type":"add","bookmark":{}}},{{"type":"update","bookmark":{}}},{{"type":"delete","id":"abc123","url":"https://some.web.site"}}]}}"#,
+            BOOKMARK, BOOKMARK
+        );
+
+        let dp = serde_json::from_slice::<DeltaPage>(body.as_bytes()).unwrap();
+        assert_eq!(dp.cursor, "c-9");
+        assert!(dp.more);
+        assert!(matches!(dp.events[0], DeltaEvent::Add { .. }));
+        assert!(matches!(dp.events[1], DeltaEvent::Update { .. }));
+        assert_eq!(
+            dp.events[2],
+            DeltaEvent::Delete {
+                id: "abc123".to_string(),
+                url: "https://some.web.site".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_search_results() {
+        let body = format!(
+            r#"{{"query":"words","total":3,"start":0,"bookmarks":[{},{}]}}"#,
+            BOOKMARK, BOOKMARK
+        );
+
+        let sr = serde_json::from_slice::<SearchResults>(body.as_bytes()).unwrap();
+        assert_eq!(sr.bookmarks.len(), 2);
+        assert_eq!(sr.bookmarks[0].tags, vec!["one", "two"]);
+        assert!(sr.bookmarks[0].unread);
+        assert_eq!(sr.next_start(), Some(2));
+
+        let last = SearchResults { start: 2, ..sr };
+        assert_eq!(last.next_start(), None);
+    }
+
+    #[test]
+    fn test_bundles() {
+        let body = r#"[{"name":"languages","tags":["rust","go"],"created_at":"2024-01-01T00:00:00Z","updated_at":"2024-01-02T00:00:00Z"}]"#;
+
+        let bs = serde_json::from_slice::<Bundles>(body.as_bytes()).unwrap();
+        assert_eq!(bs.len(), 1);
+        assert_eq!(bs[0].name, "languages");
+        assert_eq!(bs[0].tags, vec!["rust", "go"]);
+    }
+
+    #[test]
+    fn test_tag_list() {
+        let body = r#"{"count":2,"tags":[{"name":"rust","count":12},{"name":"go","count":3}]}"#;
+
+        let tl = serde_json::from_slice::<TagList>(body.as_bytes()).unwrap();
+        assert_eq!(tl.count, 2);
+        assert_eq!(
+            tl.tags[0],
+            Tag {
+                name: "rust".to_string(),
+                count: 12
+            }
+        );
+    }
+
+    #[test]
+    fn test_tag_info() {
+        let body = r#"{"name":"rust","count":12,"public_count":10,"private_count":2,"first_used":"2021-01-01T00:00:00Z","last_used":null}"#;
+
+        let ti = serde_json::from_slice::<TagInfo>(body.as_bytes()).unwrap();
+        assert_eq!(ti.public_count + ti.private_count, ti.count);
+        assert_eq!(
+            ti.first_used,
+            Some(DateTime::from_timestamp(1609459200, 0).unwrap())
+        );
+        assert_eq!(ti.last_used, None);
+    }
+
+    #[test]
+    fn test_tags_changed() {
+        let body = r#"{"tags":["rs","rustlang"],"bookmarks":7}"#;
+
+        let tc = serde_json::from_slice::<TagsChanged>(body.as_bytes()).unwrap();
+        assert_eq!(tc.tags, vec!["rs", "rustlang"]);
+        assert_eq!(tc.bookmarks, 7);
+    }
+}
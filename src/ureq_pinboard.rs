@@ -0,0 +1,184 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt::{self, Debug};
+use std::io::{self, Read};
+
+use crate::api;
+use crate::auth::{Auth, AuthError};
+use crate::base::{base_url, endpoint_url, PinboardResult};
+use bytes::Bytes;
+use http::Response as HttpResponse;
+use thiserror::Error;
+use ureq::Agent;
+use url::Url;
+
+/// A pinboard API for a single user, using `ureq`
+///
+#[derive(Clone)]
+pub struct UreqPinboard {
+    /// The agent to use for the API calls
+    agent: Agent,
+    /// The base URL to use for API calls
+    url: Url,
+    /// The authentication information to use for communication
+    auth: Auth,
+}
+
+impl Debug for UreqPinboard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("UreqPinboard")
+            .field("url", &self.url)
+            .finish()
+    }
+}
+
+impl UreqPinboard {
+    /// Create a new Pinboard API representation
+    ///
+    /// The `token` is the personal access token available on <http://pinboard.in>
+    pub fn new<H, T>(host: H, token: T) -> PinboardResult<Self>
+    where
+        H: AsRef<str>,
+        T: Into<String>,
+    {
+        Self::new_impl("https", host.as_ref(), Auth::Token(token.into()))
+    }
+
+    /// Create a new Pinboard API representation which communicates over HTTP
+    ///
+    /// This is only meant for local servers, such as the one provided by the
+    /// `testing` feature. The token is sent in the clear.
+    pub fn new_insecure<H, T>(host: H, token: T) -> PinboardResult<Self>
+    where
+        H: AsRef<str>,
+        T: Into<String>,
+    {
+        Self::new_impl("http", host.as_ref(), Auth::Token(token.into()))
+    }
+
+    /// Internal method to create a new client
+    fn new_impl(protocol: &str, host: &str, auth: Auth) -> PinboardResult<Self> {
+        let url = base_url(protocol, host)?;
+        let agent = Agent::new();
+        let api = UreqPinboard { agent, url, auth };
+
+        Ok(api)
+    }
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum UreqError {
+    #[error("error setting auth header: {}", source)]
+    AuthError {
+        #[from]
+        source: AuthError,
+    },
+    #[error("communication with pinboard: {}", source)]
+    Communication { source: Box<ureq::Transport> },
+    #[error("failed to read response: {}", source)]
+    Io {
+        #[from]
+        source: io::Error,
+    },
+    #[error("`http` error: {}", source)]
+    Http {
+        #[from]
+        source: http::Error,
+    },
+}
+
+impl api::RestClient for UreqPinboard {
+    type Error = UreqError;
+
+    fn rest_endpoint(
+        &self,
+        version: api::ApiVersion,
+        endpoint: &str,
+    ) -> Result<Url, api::ApiError<Self::Error>> {
+        Ok(endpoint_url(&self.url, &self.auth, version, endpoint)?)
+    }
+}
+
+impl api::Client for UreqPinboard {
+    fn rest(
+        &self,
+        mut request: http::request::Builder,
+        body: Vec<u8>,
+    ) -> Result<HttpResponse<Bytes>, api::ApiError<Self::Error>> {
+        let call = || -> Result<_, UreqError> {
+            self.auth.set_header(request.headers_mut().unwrap())?;
+            let http_request = request.body(body)?;
+
+            let uri = http_request.uri().to_string();
+            let mut req = self.agent.request(http_request.method().as_str(), &uri);
+            for (key, value) in http_request.headers() {
+                if let Ok(value) = value.to_str() {
+                    req = req.set(key.as_str(), value);
+                }
+            }
+            // Error statuses are still responses for the API to decode.
+            let rsp = match req.send_bytes(http_request.body()) {
+                Ok(rsp) | Err(ureq::Error::Status(_, rsp)) => rsp,
+                Err(ureq::Error::Transport(err)) => {
+                    return Err(UreqError::Communication {
+                        source: Box::new(err),
+                    })
+                },
+            };
+
+            let mut http_rsp = HttpResponse::builder().status(rsp.status());
+            let headers = http_rsp.headers_mut().unwrap();
+            for key in rsp.headers_names() {
+                let Ok(name) = http::HeaderName::from_bytes(key.as_bytes()) else {
+                    continue;
+                };
+                for value in rsp.all(&key) {
+                    if let Ok(value) = value.parse() {
+                        headers.append(&name, value);
+                    }
+                }
+            }
+            let mut body = Vec::new();
+            rsp.into_reader().read_to_end(&mut body)?;
+            Ok(http_rsp.body(body.into())?)
+        };
+        call().map_err(api::ApiError::client)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use http::StatusCode;
+
+    use crate::api::{self, v1, Query};
+    use crate::testing::FakePinboard;
+    use crate::types::v1::PostsUpdate;
+    use crate::UreqPinboard;
+
+    const TOKEN: &str = "user:0123456789ABCDEF";
+
+    #[test]
+    fn query() {
+        let server = FakePinboard::start(TOKEN).unwrap();
+        let client = UreqPinboard::new_insecure(server.host(), TOKEN).unwrap();
+
+        let update = v1::posts::Update::builder().build().unwrap();
+        let _: PostsUpdate = update.query(&client).unwrap();
+    }
+
+    #[test]
+    fn error_status() {
+        let server = FakePinboard::start(TOKEN).unwrap();
+        let client = UreqPinboard::new_insecure(server.host(), "user:WRONG").unwrap();
+
+        let update = v1::posts::Update::builder().build().unwrap();
+        let err = api::ignore(update).query(&client).unwrap_err();
+        assert!(err.is_auth_failure());
+        assert_eq!(err.status(), Some(StatusCode::UNAUTHORIZED));
+    }
+}
@@ -0,0 +1,87 @@
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Helpers for the clients which wrap another client
+
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use http::request::Builder as RequestBuilder;
+
+use crate::api::v1::{posts, Limit};
+
+/// The time to leave between two calls to an endpoint.
+pub(crate) fn interval<L>() -> Duration
+where
+    L: Limit,
+{
+    Duration::from_secs(L::secs_between_calls() as u64)
+}
+
+/// The endpoints with a limit of their own, by path, and their interval.
+pub(crate) fn endpoint_intervals() -> [(&'static str, Duration); 2] {
+    [
+        ("v1/posts/all", interval::<posts::All>()),
+        ("v1/posts/recent", interval::<posts::Recent>()),
+    ]
+}
+
+/// The path of a request without the leading slash, e.g. `v1/posts/all`.
+pub(crate) fn request_path(request: &RequestBuilder) -> String {
+    request
+        .uri_ref()
+        .map(|uri| uri.path().trim_start_matches('/').to_string())
+        .unwrap_or_default()
+}
+
+/// Lock `mutex`, even if a panic poisoned it.
+///
+/// The state behind the wrapping clients' locks stays consistent across
+/// a panic, so it is still usable.
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use http::Request;
+
+    use crate::wrapper::{endpoint_intervals, lock, request_path};
+
+    #[test]
+    fn intervals() {
+        assert_eq!(
+            endpoint_intervals(),
+            [
+                ("v1/posts/all", Duration::from_secs(300)),
+                ("v1/posts/recent", Duration::from_secs(60)),
+            ],
+        );
+    }
+
+    #[test]
+    fn path() {
+        let request = Request::builder().uri("https://api.pinboard.in/v1/posts/all?format=json");
+        assert_eq!(request_path(&request), "v1/posts/all");
+        assert_eq!(request_path(&Request::builder()), "");
+    }
+
+    #[test]
+    fn poisoned_lock() {
+        let mutex = Mutex::new(1);
+        let _ = std::panic::catch_unwind(|| {
+            let _guard = mutex.lock().unwrap();
+            panic!("poison the lock");
+        });
+        assert!(mutex.is_poisoned());
+        assert_eq!(*lock(&mutex), 1);
+    }
+}